@@ -0,0 +1,206 @@
+//! Caret - ANSI SGR escape sequence rendering
+//!
+//! Datasets captured from terminal sessions or tool-call transcripts often
+//! embed raw SGR color/style escapes (`\x1b[31m`, `\x1b[1;4m`, ...), which
+//! otherwise render as literal escape noise. This module turns them into
+//! styled Ratatui spans, following the same "parse into `Line<'static>`"
+//! shape as `tokenizer::colorize_tokens` and `markdown::render_markdown`.
+//!
+//! Gated behind `App::ansi_render` (off by default) so users who want to
+//! see the raw escape bytes verbatim still can.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+const ESC: char = '\u{1b}';
+
+/// Parse `text` for SGR escape sequences and return it as styled spans.
+///
+/// Only well-formed `ESC [ <params> m` (CSI SGR) sequences are consumed;
+/// anything else — a bare `ESC`, a sequence missing its terminating `m`, or
+/// one with a non-SGR final byte — is passed through as literal text, so
+/// truncated or malformed input never loses bytes.
+pub fn render_ansi(text: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut style = Style::default();
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != ESC || chars.peek() != Some(&'[') {
+            current.push(c);
+            continue;
+        }
+
+        // Look ahead without consuming, so a malformed/truncated sequence
+        // can fall through to the literal-text path untouched.
+        let mut lookahead = chars.clone();
+        lookahead.next(); // the '['
+        let mut params = String::new();
+        let mut terminated = false;
+        for lc in lookahead {
+            if lc == 'm' {
+                terminated = true;
+                break;
+            }
+            if lc.is_ascii_digit() || lc == ';' {
+                params.push(lc);
+            } else {
+                break; // not an SGR sequence — bail out to the literal path
+            }
+        }
+
+        if !terminated {
+            current.push(c);
+            continue;
+        }
+
+        // Confirmed well-formed — consume '[', the params, and the 'm' for real.
+        chars.next();
+        for _ in 0..params.chars().count() {
+            chars.next();
+        }
+        chars.next();
+
+        if !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        apply_sgr(&mut style, &params);
+    }
+
+    if !current.is_empty() {
+        spans.push(Span::styled(current, style));
+    }
+
+    Line::from(spans)
+}
+
+/// Apply one `ESC [ <params> m` sequence's parameters to `style` in place.
+fn apply_sgr(style: &mut Style, params: &str) {
+    let codes: Vec<u32> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').filter_map(|p| p.parse().ok()).collect()
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = Style::default(),
+            1 => *style = style.add_modifier(Modifier::BOLD),
+            4 => *style = style.add_modifier(Modifier::UNDERLINED),
+            7 => *style = style.add_modifier(Modifier::REVERSED),
+            22 => *style = style.remove_modifier(Modifier::BOLD),
+            24 => *style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => *style = style.remove_modifier(Modifier::REVERSED),
+            30..=37 => *style = style.fg(standard_color(codes[i] - 30, false)),
+            39 => *style = style.fg(Color::Reset),
+            40..=47 => *style = style.bg(standard_color(codes[i] - 40, false)),
+            49 => *style = style.bg(Color::Reset),
+            90..=97 => *style = style.fg(standard_color(codes[i] - 90, true)),
+            100..=107 => *style = style.bg(standard_color(codes[i] - 100, true)),
+            38 | 48 => {
+                let is_fg = codes[i] == 38;
+                if let Some((color, consumed)) = extended_color(&codes[i + 1..]) {
+                    *style = if is_fg { style.fg(color) } else { style.bg(color) };
+                    i += consumed;
+                }
+            }
+            _ => {} // unrecognized code — ignored, not an error
+        }
+        i += 1;
+    }
+}
+
+/// Map a base SGR color number (0-7) to its `Color`, in either the normal
+/// or bright (`90-97`/`100-107`) variant.
+fn standard_color(n: u32, bright: bool) -> Color {
+    match (n, bright) {
+        (0, false) => Color::Black,
+        (1, false) => Color::Red,
+        (2, false) => Color::Green,
+        (3, false) => Color::Yellow,
+        (4, false) => Color::Blue,
+        (5, false) => Color::Magenta,
+        (6, false) => Color::Cyan,
+        (7, false) => Color::Gray,
+        (0, true) => Color::DarkGray,
+        (1, true) => Color::LightRed,
+        (2, true) => Color::LightGreen,
+        (3, true) => Color::LightYellow,
+        (4, true) => Color::LightBlue,
+        (5, true) => Color::LightMagenta,
+        (6, true) => Color::LightCyan,
+        (7, true) => Color::White,
+        _ => Color::Reset,
+    }
+}
+
+/// Parse a `5;N` (256-color) or `2;r;g;b` (truecolor) extended color
+/// sequence starting at `codes` (everything after the `38`/`48` itself).
+/// Returns the resolved color and how many of `codes` it consumed.
+fn extended_color(codes: &[u32]) -> Option<(Color, usize)> {
+    match codes.first() {
+        Some(5) => {
+            let n = *codes.get(1)?;
+            Some((Color::Indexed(n as u8), 2))
+        }
+        Some(2) => {
+            let r = *codes.get(1)? as u8;
+            let g = *codes.get(2)? as u8;
+            let b = *codes.get(3)? as u8;
+            Some((Color::Rgb(r, g, b), 4))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_passes_through() {
+        let line = render_ansi("hello world");
+        assert_eq!(line.spans.len(), 1);
+        assert_eq!(line.spans[0].content, "hello world");
+    }
+
+    #[test]
+    fn test_basic_fg_color_sequence() {
+        let line = render_ansi("\u{1b}[31mred\u{1b}[0m plain");
+        assert_eq!(line.spans[0].content, "red");
+        assert_eq!(line.spans[0].style.fg, Some(Color::Red));
+        assert_eq!(line.spans[1].content, " plain");
+        assert_eq!(line.spans[1].style.fg, None);
+    }
+
+    #[test]
+    fn test_bold_and_underline_combine() {
+        let line = render_ansi("\u{1b}[1;4mstrong\u{1b}[0m");
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_truncated_sequence_passes_through_unchanged() {
+        let line = render_ansi("abc\u{1b}[31");
+        let joined: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(joined, "abc\u{1b}[31");
+    }
+
+    #[test]
+    fn test_bare_escape_passes_through() {
+        let line = render_ansi("a\u{1b}b");
+        let joined: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(joined, "a\u{1b}b");
+    }
+
+    #[test]
+    fn test_truecolor_background() {
+        let line = render_ansi("\u{1b}[48;2;10;20;30mx");
+        assert_eq!(line.spans[0].style.bg, Some(Color::Rgb(10, 20, 30)));
+    }
+}
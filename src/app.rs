@@ -2,7 +2,9 @@
 
 use crate::data::Dataset;
 use crate::engine::{DedupEngine, DedupResult, DedupStrategy};
+use crate::jsontree::{self, TreeRow};
 use crate::linter::LintResult;
+use crate::theme::Theme;
 use crate::tokenizer::TokenizerWrapper;
 
 /// View mode for the main display
@@ -58,9 +60,26 @@ pub struct App {
     pub selected_line: usize,
     /// Whether to show the detail panel
     pub show_detail: bool,
-    /// Tree expansion state for JSON tree view
-    #[allow(dead_code)]
+    /// Paths (e.g. `messages[2].content`) that are currently expanded in
+    /// the `ViewMode::Tree` view. Every container not listed here renders
+    /// collapsed, except the root, which is always shown expanded — see
+    /// `jsontree` for the default-state rules.
     pub tree_expanded: std::collections::HashSet<String>,
+    /// Index into the current line's flattened tree rows (see
+    /// `App::tree_rows`) that has keyboard focus in `ViewMode::Tree`.
+    pub tree_cursor: usize,
+    /// Whether the detail panel renders string fields as Markdown instead
+    /// of raw escaped JSON strings.
+    pub show_markdown: bool,
+    /// Whether the Text view and detail panel render ANSI SGR escape
+    /// sequences as styled spans instead of showing the raw escape bytes.
+    pub ansi_render: bool,
+    /// Active theme, loaded from `~/.config/caret/theme.{toml,json}` (or a
+    /// built-in default).
+    pub theme: Theme,
+    /// Name of the built-in theme currently in rotation for the cycle key,
+    /// tracked separately since a loaded custom theme may not match one.
+    theme_cycle_name: String,
 }
 
 impl App {
@@ -79,20 +98,45 @@ impl App {
             selected_line: 0,
             show_detail: false,
             tree_expanded: std::collections::HashSet::new(),
+            tree_cursor: 0,
+            show_markdown: false,
+            ansi_render: false,
+            theme: Theme::load(),
+            theme_cycle_name: "dracula".to_string(),
         }
     }
 
+    /// Cycle to the next built-in theme (dracula -> solarized -> gruvbox -> ...).
+    pub fn cycle_theme(&mut self) {
+        let next = Theme::next_builtin_name(&self.theme_cycle_name);
+        self.theme_cycle_name = next.to_string();
+        self.theme = Theme::named(next).expect("built-in theme name always resolves");
+    }
+
     /// Toggle detail panel visibility
     pub fn toggle_detail(&mut self) {
         self.show_detail = !self.show_detail;
     }
 
+    /// Toggle Markdown rendering of string fields in the detail panel.
+    pub fn toggle_markdown(&mut self) {
+        self.show_markdown = !self.show_markdown;
+    }
+
+    /// Toggle ANSI escape rendering in the Text view and detail panel.
+    pub fn toggle_ansi_render(&mut self) {
+        self.ansi_render = !self.ansi_render;
+    }
+
     /// Toggle dedup scan: run if no result, clear if already scanned.
     pub fn toggle_dedup(&mut self) {
         if self.dedup_result.is_some() {
             self.dedup_result = None;
         } else {
-            let engine = DedupEngine::new(DedupStrategy::SimHash { threshold: 3 });
+            let engine = DedupEngine::new(DedupStrategy::SimHash {
+                threshold: 3,
+                fingerprint_bits: 64,
+            });
             let result = engine.scan(&self.dataset);
             self.dedup_result = Some(result);
         }
@@ -116,6 +160,60 @@ impl App {
         }
     }
 
+    /// Flatten the currently selected line into `ViewMode::Tree` rows.
+    /// Returns an empty list if the line isn't valid JSON.
+    pub fn tree_rows(&self) -> Vec<TreeRow> {
+        let Some(line) = self.current_line_content() else {
+            return Vec::new();
+        };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            return Vec::new();
+        };
+        jsontree::flatten(&value, &self.tree_expanded)
+    }
+
+    /// Move the tree cursor down by one row, clamped to the current line's
+    /// row count.
+    pub fn tree_cursor_down(&mut self) {
+        let len = self.tree_rows().len();
+        if len > 0 {
+            self.tree_cursor = (self.tree_cursor + 1).min(len - 1);
+        }
+    }
+
+    /// Move the tree cursor up by one row.
+    pub fn tree_cursor_up(&mut self) {
+        self.tree_cursor = self.tree_cursor.saturating_sub(1);
+    }
+
+    /// Expand the node at `path`.
+    pub fn expand_node(&mut self, path: String) {
+        self.tree_expanded.insert(path);
+    }
+
+    /// Collapse the node at `path`.
+    pub fn collapse_node(&mut self, path: &str) {
+        self.tree_expanded.remove(path);
+    }
+
+    /// Toggle whichever node the tree cursor currently points at. No-op if
+    /// that row isn't expandable (a leaf, or the row-count-truncation
+    /// marker).
+    pub fn toggle_node_at_cursor(&mut self) {
+        let rows = self.tree_rows();
+        let Some(row) = rows.get(self.tree_cursor) else {
+            return;
+        };
+        if !row.expandable {
+            return;
+        }
+        if row.expanded {
+            self.collapse_node(&row.path);
+        } else {
+            self.expand_node(row.path.clone());
+        }
+    }
+
     /// Set the tokenizer for X-Ray mode
     pub fn with_tokenizer(mut self, tokenizer: TokenizerWrapper) -> Self {
         self.tokenizer = Some(tokenizer);
@@ -3,34 +3,65 @@
 //! The MCP server sends commands through an mpsc channel, which the TUI
 //! event loop polls to react to AI-driven navigation requests.
 
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
-/// Commands that can be sent from MCP to the TUI
-#[derive(Debug, Clone)]
+/// Commands that can be sent from MCP to the TUI.
+///
+/// The `Get*` variants are queries rather than one-way commands: each
+/// carries a `oneshot::Sender` that the TUI event loop fulfills against
+/// `App` before moving on, so an MCP tool can "navigate → inspect →
+/// decide" instead of only driving navigation blind. Because a
+/// `oneshot::Sender` is neither `Clone` nor `Debug`, `TuiCommand` itself
+/// can't derive either.
 pub enum TuiCommand {
     /// Jump to a specific line number (0-indexed)
     JumpToLine(usize),
-    
+
     /// Toggle view mode: Text → TokenXray → Tree → Text
     ToggleView,
-    
+
     /// Set view mode directly
     SetViewMode(ViewModeCmd),
-    
+
     /// Show or hide the detail panel
     ShowDetail(bool),
-    
+
     /// Scroll down by N lines
     ScrollDown(usize),
-    
+
     /// Scroll up by N lines
     ScrollUp(usize),
-    
+
     /// Jump to top of dataset
     GotoTop,
-    
+
     /// Jump to bottom of dataset
     GotoBottom,
+
+    /// Query the currently selected line — content, line number, and
+    /// duplicate/lint-error status — as a JSON object.
+    GetCurrentLine(oneshot::Sender<serde_json::Value>),
+
+    /// Query lint errors found for a specific line index (0-based), as a
+    /// JSON object.
+    GetLintErrorsForLine(usize, oneshot::Sender<serde_json::Value>),
+
+    /// Query the current dedup scan result (if any scan has been run in
+    /// the TUI), as a JSON object.
+    GetDedupClusters(oneshot::Sender<serde_json::Value>),
+
+    /// Query the active view mode, as a JSON object.
+    GetViewMode(oneshot::Sender<serde_json::Value>),
+
+    /// Expand the `ViewMode::Tree` node at the given JSON path (e.g.
+    /// `messages[2].content`).
+    ExpandNode(String),
+
+    /// Collapse the `ViewMode::Tree` node at the given JSON path.
+    CollapseNode(String),
+
+    /// Toggle whichever tree node currently has the TUI's tree cursor.
+    ToggleNodeAtCursor,
 }
 
 /// View mode variants for SetViewMode command
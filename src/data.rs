@@ -6,11 +6,15 @@
 
 use anyhow::{Context, Result};
 use memmap2::Mmap;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ProjectionMask;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-use crate::format::{self, InputFormat};
+use crate::format::{self, CsvDialect, CsvOptions, InputFormat};
 
 /// Storage backend for the dataset
 enum DataStorage {
@@ -18,6 +22,8 @@ enum DataStorage {
     Mmap(Mmap),
     /// In-memory buffer (for stdin, Parquet, CSV, or small files)
     InMemory(Vec<u8>),
+    /// Lazily-decoded Parquet file — see [`ParquetLazyReader`].
+    ParquetLazy(ParquetLazyReader),
 }
 
 #[allow(dead_code)]
@@ -26,6 +32,9 @@ impl DataStorage {
         match self {
             DataStorage::Mmap(m) => m.as_ref(),
             DataStorage::InMemory(v) => v.as_slice(),
+            DataStorage::ParquetLazy(_) => {
+                panic!("as_bytes called on a lazily-decoded Parquet Dataset — get_line/line_count special-case ParquetLazy instead of going through the byte-offset path")
+            }
         }
     }
 
@@ -33,7 +42,148 @@ impl DataStorage {
         match self {
             DataStorage::Mmap(m) => m.len(),
             DataStorage::InMemory(v) => v.len(),
+            DataStorage::ParquetLazy(r) => r.total_rows,
+        }
+    }
+}
+
+/// Options controlling how a Parquet file is opened.
+#[derive(Debug, Clone, Default)]
+pub struct ParquetOptions {
+    /// Decode row groups lazily, on first access, instead of eagerly
+    /// converting the whole file to JSONL up front. See
+    /// [`DataStorage::ParquetLazy`] — makes opening huge Parquet files
+    /// near-instant, like the JSONL mmap path.
+    pub lazy: bool,
+    /// Only decode these columns (`None` decodes every column). Ignored
+    /// when `lazy` is false.
+    pub columns: Option<Vec<String>>,
+    /// Arrow batch size used when decoding a row group, bounding per-batch
+    /// memory. Ignored when `lazy` is false.
+    pub batch_size: usize,
+}
+
+/// Default Arrow batch size for lazy Parquet decoding.
+const DEFAULT_PARQUET_LAZY_BATCH_SIZE: usize = 8192;
+
+/// Lazily-decoded Parquet backend for `DataStorage::ParquetLazy`.
+///
+/// Keeps only the file's row-group metadata around at open time — no
+/// decoding happens until a specific row is requested via `get_line`, which
+/// decodes that row's whole row group (bounded by `batch_size` per Arrow
+/// batch and `projection` for column pruning) and caches the result so
+/// repeat access to the same region of the file, the common TUI scroll
+/// pattern, doesn't redecode.
+///
+/// The cache is append-only — entries are added on first access but never
+/// evicted — so memory use grows with the set of row groups actually
+/// visited rather than the whole file. This is deliberate, not an oversight:
+/// `get_line` returns a `&str` borrowed straight out of the cache (see its
+/// safety note), which is only sound as long as a cached entry's memory
+/// never moves or frees for the lifetime of `self`.
+struct ParquetLazyReader {
+    path: PathBuf,
+    /// Global row index each row group starts at; row group `i` spans
+    /// `[row_group_start[i], row_group_start[i + 1])` (or `total_rows` for
+    /// the last one).
+    row_group_start: Vec<usize>,
+    total_rows: usize,
+    batch_size: usize,
+    projection: Option<Vec<String>>,
+    /// Decoded row groups, keyed by row-group index.
+    cache: Mutex<HashMap<usize, Vec<Box<str>>>>,
+}
+
+impl ParquetLazyReader {
+    fn open(path: &Path, options: &ParquetOptions) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open Parquet file: {}", path.display()))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .with_context(|| "Failed to read Parquet metadata")?;
+
+        let mut row_group_start = Vec::new();
+        let mut total_rows = 0usize;
+        for rg in builder.metadata().row_groups() {
+            row_group_start.push(total_rows);
+            total_rows += rg.num_rows().max(0) as usize;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            row_group_start,
+            total_rows,
+            batch_size: if options.batch_size == 0 {
+                DEFAULT_PARQUET_LAZY_BATCH_SIZE
+            } else {
+                options.batch_size
+            },
+            projection: options.columns.clone(),
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Binary search for the row group containing global row `row`.
+    fn row_group_for(&self, row: usize) -> Option<usize> {
+        if row >= self.total_rows {
+            return None;
+        }
+        match self.row_group_start.binary_search(&row) {
+            Ok(i) => Some(i),
+            Err(i) => Some(i - 1),
+        }
+    }
+
+    fn decode_row_group(&self, rg_index: usize) -> Result<Vec<Box<str>>> {
+        let file = File::open(&self.path)
+            .with_context(|| format!("Failed to open Parquet file: {}", self.path.display()))?;
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .with_context(|| "Failed to read Parquet metadata")?
+            .with_batch_size(self.batch_size);
+
+        if let Some(cols) = self.projection.as_deref().filter(|c| !c.is_empty()) {
+            let mask =
+                ProjectionMask::columns(builder.parquet_schema(), cols.iter().map(|s| s.as_str()));
+            builder = builder.with_projection(mask);
         }
+
+        let reader = builder
+            .with_row_groups(vec![rg_index])
+            .build()
+            .with_context(|| "Failed to build Parquet reader")?;
+
+        let mut lines = Vec::new();
+        for batch_result in reader {
+            let batch = batch_result.with_context(|| "Failed to read Parquet batch")?;
+            lines.extend(
+                format::record_batch_to_jsonl_lines(&batch)?
+                    .into_iter()
+                    .map(|s| s.into_boxed_str()),
+            );
+        }
+        Ok(lines)
+    }
+
+    fn get_line(&self, row: usize) -> Option<&str> {
+        let rg_index = self.row_group_for(row)?;
+        let local_index = row - self.row_group_start[rg_index];
+
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if !cache.contains_key(&rg_index) {
+            let lines = self.decode_row_group(rg_index).ok()?;
+            cache.insert(rg_index, lines);
+        }
+        let lines = cache.get(&rg_index)?;
+        let line: &str = lines.get(local_index)?;
+
+        // SAFETY: extends the borrow from the (temporary) `MutexGuard`'s
+        // lifetime to `self`'s. Sound because `cache` entries are
+        // append-only (see the struct doc comment) — once a row group is
+        // inserted its `Box<str>` lines are never mutated, moved, or freed
+        // for the lifetime of `self`, so this reference stays valid even
+        // after the lock is released and the underlying `HashMap`
+        // reallocates its bucket array on a later insert (that only moves
+        // the `Box` handles, not the heap memory they point to).
+        Some(unsafe { &*(line as *const str) })
     }
 }
 
@@ -65,12 +215,28 @@ impl Dataset {
 
     /// Open a file with explicit format specification
     pub fn open_with_format<P: AsRef<Path>>(path: P, format: InputFormat) -> Result<Self> {
+        let csv_options = CsvOptions {
+            dialect: CsvDialect::for_path(&path),
+            ..Default::default()
+        };
+        Self::open_with_format_and_options(path, format, csv_options, ParquetOptions::default())
+    }
+
+    /// Open a file with explicit format specification, CSV conversion
+    /// options, and Parquet loading options. `csv_options` is ignored for
+    /// non-CSV formats, `parquet_options` for non-Parquet ones.
+    pub fn open_with_format_and_options<P: AsRef<Path>>(
+        path: P,
+        format: InputFormat,
+        csv_options: CsvOptions,
+        parquet_options: ParquetOptions,
+    ) -> Result<Self> {
         let path_ref = path.as_ref();
 
         match format {
             InputFormat::Jsonl => Self::open_jsonl(path_ref),
-            InputFormat::Parquet => Self::open_parquet(path_ref),
-            InputFormat::Csv => Self::open_csv(path_ref),
+            InputFormat::Parquet => Self::open_parquet(path_ref, parquet_options),
+            InputFormat::Csv => Self::open_csv(path_ref, csv_options),
         }
     }
 
@@ -102,15 +268,31 @@ impl Dataset {
         })
     }
 
-    /// Open a Parquet file (converts to JSONL in memory)
-    fn open_parquet(path: &Path) -> Result<Self> {
+    /// Open a Parquet file. By default converts the whole file to JSONL in
+    /// memory; with `options.lazy` set, keeps only row-group metadata and
+    /// decodes on demand (see [`DataStorage::ParquetLazy`]).
+    fn open_parquet(path: &Path, options: ParquetOptions) -> Result<Self> {
+        if options.lazy {
+            let size = std::fs::metadata(path)
+                .with_context(|| format!("Failed to stat file: {}", path.display()))?
+                .len();
+            let reader = ParquetLazyReader::open(path, &options)?;
+            return Ok(Self {
+                storage: DataStorage::ParquetLazy(reader),
+                line_offsets: Vec::new(),
+                path: path.display().to_string(),
+                size,
+                format: InputFormat::Parquet,
+            });
+        }
+
         let lines = format::parquet_to_jsonl(path)?;
         Self::from_lines(lines, path.display().to_string(), InputFormat::Parquet)
     }
 
     /// Open a CSV file (converts to JSONL in memory)
-    fn open_csv(path: &Path) -> Result<Self> {
-        let lines = format::csv_to_jsonl(path)?;
+    fn open_csv(path: &Path, csv_options: CsvOptions) -> Result<Self> {
+        let lines = format::csv_to_jsonl_with_options(path, csv_options)?;
         Self::from_lines(lines, path.display().to_string(), InputFormat::Csv)
     }
 
@@ -138,6 +320,66 @@ impl Dataset {
         })
     }
 
+    /// Build a dataset directly from pre-computed parts — used when the
+    /// caller already has a contiguous JSONL buffer and matching line
+    /// offsets (e.g. a snapshot of another `Dataset`'s lines for the MCP
+    /// server, or a fully-materialized HF stream).
+    pub fn from_raw_parts(
+        buffer: Vec<u8>,
+        line_offsets: Vec<usize>,
+        path: String,
+        size: u64,
+        format: InputFormat,
+    ) -> Self {
+        Self {
+            storage: DataStorage::InMemory(buffer),
+            line_offsets,
+            path,
+            size,
+            format,
+        }
+    }
+
+    /// Create an empty in-memory dataset that [`append_lines`](Self::append_lines)
+    /// can grow incrementally — used by HF streaming so each row group is
+    /// appended as it arrives instead of buffering every line in a separate
+    /// `Vec` and rebuilding the line index from scratch at the end.
+    pub fn empty(path: String, format: InputFormat) -> Self {
+        Self {
+            storage: DataStorage::InMemory(Vec::new()),
+            line_offsets: Vec::new(),
+            path,
+            size: 0,
+            format,
+        }
+    }
+
+    /// Append JSONL lines to an in-memory dataset, extending the line index
+    /// as it goes rather than rescanning the whole buffer.
+    ///
+    /// Panics if called on a memory-mapped dataset — `empty`/`from_raw_parts`
+    /// always use the in-memory backend, so this is only ever reachable from
+    /// data built that way.
+    pub fn append_lines<I: IntoIterator<Item = String>>(&mut self, lines: I) {
+        let buf = match &mut self.storage {
+            DataStorage::InMemory(v) => v,
+            DataStorage::Mmap(_) => panic!("append_lines called on a memory-mapped Dataset"),
+            DataStorage::ParquetLazy(_) => {
+                panic!("append_lines called on a lazily-decoded Parquet Dataset")
+            }
+        };
+
+        for line in lines {
+            if !buf.is_empty() {
+                buf.push(b'\n');
+            }
+            self.line_offsets.push(buf.len());
+            buf.extend_from_slice(line.as_bytes());
+        }
+
+        self.size = buf.len() as u64;
+    }
+
     /// Read dataset from stdin
     ///
     /// Supports pipeline workflows: `cat data.jsonl | caret -`
@@ -166,14 +408,23 @@ impl Dataset {
 
     /// Get the total number of lines in the file
     pub fn line_count(&self) -> usize {
-        self.line_offsets.len()
+        match &self.storage {
+            DataStorage::ParquetLazy(r) => r.total_rows,
+            _ => self.line_offsets.len(),
+        }
     }
 
     /// Get a specific line by index (0-indexed)
     ///
     /// Returns None if index is out of bounds.
-    /// This is O(1) thanks to the pre-computed line offsets.
+    /// This is O(1) thanks to the pre-computed line offsets (or, for a
+    /// lazily-decoded Parquet dataset, amortized O(1) after the containing
+    /// row group has been decoded once).
     pub fn get_line(&self, index: usize) -> Option<&str> {
+        if let DataStorage::ParquetLazy(reader) = &self.storage {
+            return reader.get_line(index);
+        }
+
         if index >= self.line_offsets.len() {
             return None;
         }
@@ -228,6 +479,31 @@ impl Dataset {
             InputFormat::Csv => "CSV",
         }
     }
+
+    /// Export this dataset's lines to `path` in `format` (Parquet, CSV, or
+    /// JSONL). When `indices` is `Some`, only those line indices are
+    /// exported (in the given order), so a filtered/selected subset of the
+    /// loaded dataset can be written out without having to rebuild a whole
+    /// separate `Dataset` first; `None` exports every line.
+    pub fn export<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: InputFormat,
+        indices: Option<&[usize]>,
+    ) -> Result<()> {
+        let lines: Vec<String> = match indices {
+            Some(idx) => idx
+                .iter()
+                .filter_map(|&i| self.get_line(i))
+                .map(|s| s.to_string())
+                .collect(),
+            None => (0..self.line_count())
+                .filter_map(|i| self.get_line(i))
+                .map(|s| s.to_string())
+                .collect(),
+        };
+        format::export_lines(&lines, path, format)
+    }
 }
 
 #[cfg(test)]
@@ -252,6 +528,19 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_append_lines_builds_line_index_incrementally() {
+        let mut dataset = Dataset::empty("<test>".to_string(), InputFormat::Parquet);
+        dataset.append_lines(vec![r#"{"a":1}"#.to_string()]);
+        assert_eq!(dataset.line_count(), 1);
+        assert_eq!(dataset.get_line(0), Some(r#"{"a":1}"#));
+
+        dataset.append_lines(vec![r#"{"a":2}"#.to_string(), r#"{"a":3}"#.to_string()]);
+        assert_eq!(dataset.line_count(), 3);
+        assert_eq!(dataset.get_line(1), Some(r#"{"a":2}"#));
+        assert_eq!(dataset.get_line(2), Some(r#"{"a":3}"#));
+    }
+
     #[test]
     fn test_csv_loading() -> Result<()> {
         let mut file = NamedTempFile::with_suffix(".csv")?;
@@ -266,4 +555,96 @@ mod tests {
         assert_eq!(dataset.format, InputFormat::Csv);
         Ok(())
     }
+
+    #[test]
+    fn test_csv_loading_typed() -> Result<()> {
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "count:number,active:boolean")?;
+        writeln!(file, "3,true")?;
+
+        let dataset = Dataset::open_with_format_and_options(
+            file.path(),
+            InputFormat::Csv,
+            CsvOptions { typed: true, ..Default::default() },
+            ParquetOptions::default(),
+        )?;
+        let line = dataset.get_line(0).unwrap();
+        let value: serde_json::Value = serde_json::from_str(line)?;
+        assert_eq!(value["count"], serde_json::json!(3));
+        assert_eq!(value["active"], serde_json::Value::Bool(true));
+        Ok(())
+    }
+
+    /// Write a tiny single-row-group Parquet file for the lazy-loading
+    /// tests below.
+    fn write_test_parquet(path: &Path) -> Result<()> {
+        use arrow::array::{Int64Array, StringArray};
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::record_batch::RecordBatch;
+        use parquet::arrow::ArrowWriter;
+        use std::sync::Arc;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("text", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            ],
+        )?;
+
+        let file = File::create(path)?;
+        let mut writer = ArrowWriter::try_new(file, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parquet_lazy_loading() -> Result<()> {
+        let file = NamedTempFile::with_suffix(".parquet")?;
+        write_test_parquet(file.path())?;
+
+        let dataset = Dataset::open_with_format_and_options(
+            file.path(),
+            InputFormat::Parquet,
+            CsvOptions::default(),
+            ParquetOptions {
+                lazy: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(dataset.line_count(), 3);
+        let value: serde_json::Value = serde_json::from_str(dataset.get_line(1).unwrap())?;
+        assert_eq!(value["id"], serde_json::json!(2));
+        assert_eq!(value["text"], serde_json::json!("b"));
+        assert!(dataset.get_line(3).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parquet_lazy_matches_eager() -> Result<()> {
+        let file = NamedTempFile::with_suffix(".parquet")?;
+        write_test_parquet(file.path())?;
+
+        let eager = Dataset::open(file.path())?;
+        let lazy = Dataset::open_with_format_and_options(
+            file.path(),
+            InputFormat::Parquet,
+            CsvOptions::default(),
+            ParquetOptions {
+                lazy: true,
+                ..Default::default()
+            },
+        )?;
+
+        assert_eq!(eager.line_count(), lazy.line_count());
+        for i in 0..eager.line_count() {
+            assert_eq!(eager.get_line(i), lazy.get_line(i));
+        }
+        Ok(())
+    }
 }
@@ -25,8 +25,14 @@
 //! Phase 2 (index construction) is sequential to preserve first-seen ordering,
 //! using hardware POPCNT for sub-nanosecond Hamming distance checks.
 
+use aho_corasick::AhoCorasick;
+use anyhow::{bail, Context, Result};
 use rayon::prelude::*;
+use xxhash_rust::xxh3::{xxh3_128, xxh3_64};
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use crate::data::Dataset;
 
@@ -110,15 +116,42 @@ impl Fingerprint {
     }
 }
 
+/// 128-bit counterpart to `Fingerprint`, for `DedupStrategy::SimHash`
+/// configured with `fingerprint_bits: 128`. Doubles the usable threshold
+/// resolution, which matters on long documents where 64 bits saturates
+/// and distinct-but-similar examples start colliding on hash alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint128(pub u128);
+
+impl Fingerprint128 {
+    /// Hamming distance over two `u64` lanes rather than a single `u128`
+    /// POPCNT, matching how `Fingerprint`'s distance compiles down.
+    #[inline(always)]
+    pub fn hamming_distance(self, other: Self) -> u32 {
+        let diff = self.0 ^ other.0;
+        (diff as u64).count_ones() + ((diff >> 64) as u64).count_ones()
+    }
+
+    /// Returns `true` if the distance is within `threshold`.
+    #[inline(always)]
+    pub fn is_near_duplicate(self, other: Self, threshold: u32) -> bool {
+        self.hamming_distance(other) <= threshold
+    }
+}
+
 // ─── SimHasher ──────────────────────────────────────────────────────────────
 
 /// SimHash engine tuned for LLM training data.
 ///
-/// Converts variable-length byte sequences into fixed 64-bit fingerprints
-/// where similar inputs produce similar outputs (locality-sensitive).
+/// Converts variable-length byte sequences into fixed-width fingerprints
+/// (64 or 128 bits) where similar inputs produce similar outputs
+/// (locality-sensitive).
 ///
-/// Uses FNV-1a for shingle hashing — branch-free, zero-allocation,
-/// and fast enough that the memory bus is the bottleneck, not the CPU.
+/// Uses xxh3 for shingle hashing — branch-free, zero-allocation, and
+/// faster than FNV-1a on the byte windows this runs over. The 128-bit
+/// fingerprint reuses xxh3's 128-bit output directly rather than hashing
+/// each shingle twice: the low and high 64 bits seed two independent
+/// halves of the sign-counter accumulator in the same pass.
 pub struct SimHasher {
     /// Byte-level n-gram (shingle) size.
     shingle_size: usize,
@@ -140,20 +173,20 @@ impl SimHasher {
     /// Compute a 64-bit SimHash fingerprint.
     ///
     /// For each byte-level shingle:
-    /// 1. Hash with FNV-1a (zero alloc)
+    /// 1. Hash with xxh3 (zero alloc)
     /// 2. For each of the 64 bit positions, add +1 or -1 to an accumulator
     /// 3. After all shingles, collapse accumulators: positive → 1, else → 0
     ///
     /// The accumulator loop auto-vectorizes with `-C opt-level=3`.
     pub fn fingerprint(&self, data: &[u8]) -> Fingerprint {
         if data.len() < self.shingle_size {
-            return Fingerprint(self.fnv1a(data));
+            return Fingerprint(xxh3_64(data));
         }
 
         let mut acc = [0i32; 64];
 
         for window in data.windows(self.shingle_size) {
-            let hash = self.fnv1a(window);
+            let hash = xxh3_64(window);
             // The compiler auto-vectorizes this loop at opt-level 3
             for i in 0..64 {
                 if hash & (1u64 << i) != 0 {
@@ -173,6 +206,70 @@ impl SimHasher {
         Fingerprint(fp)
     }
 
+    /// Compute a 128-bit SimHash fingerprint — see `DedupStrategy::SimHash`'s
+    /// `fingerprint_bits` field. Same accumulate-and-collapse shape as
+    /// `fingerprint`, just over twice as many sign counters.
+    pub fn fingerprint128(&self, data: &[u8]) -> Fingerprint128 {
+        if data.len() < self.shingle_size {
+            return Fingerprint128(xxh3_128(data));
+        }
+
+        let mut acc = [0i32; 128];
+
+        for window in data.windows(self.shingle_size) {
+            let hash = xxh3_128(window);
+            for i in 0..128 {
+                if hash & (1u128 << i) != 0 {
+                    acc[i] += 1;
+                } else {
+                    acc[i] -= 1;
+                }
+            }
+        }
+
+        let mut fp: u128 = 0;
+        for (i, &val) in acc.iter().enumerate() {
+            if val > 0 {
+                fp |= 1u128 << i;
+            }
+        }
+        Fingerprint128(fp)
+    }
+
+    /// Compute a 64-bit SimHash fingerprint where each shingle's ±1
+    /// contribution is scaled by `weight(shingle_hash)` instead of being
+    /// uniform — see `DedupStrategy::WeightedSimHash`. `weight` should
+    /// return near-zero for high-frequency boilerplate shingles and larger
+    /// values for rare, content-bearing ones, so the latter dominate the
+    /// final fingerprint.
+    pub fn fingerprint_weighted(&self, data: &[u8], weight: impl Fn(u64) -> f64) -> Fingerprint {
+        if data.len() < self.shingle_size {
+            return Fingerprint(xxh3_64(data));
+        }
+
+        let mut acc = [0f64; 64];
+
+        for window in data.windows(self.shingle_size) {
+            let hash = xxh3_64(window);
+            let w = weight(hash);
+            for i in 0..64 {
+                if hash & (1u64 << i) != 0 {
+                    acc[i] += w;
+                } else {
+                    acc[i] -= w;
+                }
+            }
+        }
+
+        let mut fp: u64 = 0;
+        for (i, &val) in acc.iter().enumerate() {
+            if val > 0.0 {
+                fp |= 1u64 << i;
+            }
+        }
+        Fingerprint(fp)
+    }
+
     /// Hash a full byte slice with FNV-1a (used for exact-match mode).
     pub fn hash_bytes(&self, data: &[u8]) -> u64 {
         self.fnv1a(data)
@@ -246,22 +343,281 @@ fn extract_content_bytes(data: &[u8]) -> Vec<u8> {
     result
 }
 
+// ─── Multi-Index Hash ───────────────────────────────────────────────────────
+
+/// Turns near-duplicate SimHash lookup into near-O(N) via multi-index
+/// hashing (HmSearch over 64-bit codes).
+///
+/// To find all fingerprints within Hamming distance `r` of a query,
+/// partition the 64 bits into `m = r + 1` disjoint substrings. By the
+/// pigeonhole principle, any two codes differing in at most `r` bits must
+/// agree exactly on at least one substring. So maintaining one hash table
+/// per substring, keyed by that substring's bits and storing the indices
+/// of fingerprints seen so far, turns "scan every unique fingerprint" into
+/// "look up `m` buckets and verify only the union of their contents".
+///
+/// Substrings are widened past 32 bits (to `u64`) rather than the `u32`
+/// a literal reading of the technique suggests, since `r = 0` (exact
+/// SimHash match) collapses to `m = 1` — a single 64-bit substring that a
+/// `u32` key couldn't hold.
+struct MultiIndexHash {
+    /// Bit width of each of the `m` substrings; lengths sum to 64 (the
+    /// remainder from dividing unevenly is spread across the first few
+    /// parts so no part exceeds the others by more than one bit).
+    part_widths: Vec<u32>,
+    /// One candidate table per substring, keyed by that substring's bits
+    /// as extracted from a fingerprint seen so far. Buckets store the full
+    /// fingerprint alongside its index so lookups never need an external
+    /// fingerprint slice — the index is self-contained, which matters once
+    /// `DedupIndex` reconstructs one from doc_ids that don't correspond to
+    /// positions in any particular in-memory `Vec<Fingerprint>`.
+    tables: Vec<HashMap<u64, Vec<(Fingerprint, usize)>>>,
+    threshold: u32,
+}
+
+impl MultiIndexHash {
+    fn new(threshold: u32) -> Self {
+        let m = (threshold as usize + 1).clamp(1, 64);
+        let base = 64 / m as u32;
+        let remainder = 64 % m as u32;
+        let part_widths: Vec<u32> = (0..m as u32)
+            .map(|i| if i < remainder { base + 1 } else { base })
+            .collect();
+
+        Self {
+            tables: vec![HashMap::new(); m],
+            part_widths,
+            threshold,
+        }
+    }
+
+    /// Extract this fingerprint's `m` substrings, one per table.
+    fn substrings(&self, fp: Fingerprint) -> Vec<u64> {
+        let mut shift = 0u32;
+        let mut out = Vec::with_capacity(self.part_widths.len());
+        for &width in &self.part_widths {
+            let mask = if width >= 64 { u64::MAX } else { (1u64 << width) - 1 };
+            out.push((fp.0 >> shift) & mask);
+            shift += width;
+        }
+        out
+    }
+
+    /// Look up the smallest already-indexed line whose true Hamming
+    /// distance to `fp` is within `threshold` (matching the sequential
+    /// first-seen order the original linear scan would have found), or —
+    /// if none — insert `fp` under index `idx` and return `None`.
+    fn find_or_insert(&mut self, idx: usize, fp: Fingerprint) -> Option<usize> {
+        if let Some(found) = self.find(fp) {
+            return Some(found);
+        }
+        self.insert(idx, fp);
+        None
+    }
+
+    /// Look up the smallest already-indexed entry within `threshold` of
+    /// `fp`, without inserting `fp` itself.
+    fn find(&self, fp: Fingerprint) -> Option<usize> {
+        let subs = self.substrings(fp);
+        let mut best: Option<usize> = None;
+
+        for (table, key) in self.tables.iter().zip(&subs) {
+            let Some(bucket) = table.get(key) else {
+                continue;
+            };
+            for &(ofp, oidx) in bucket {
+                if fp.is_near_duplicate(ofp, self.threshold) {
+                    best = Some(match best {
+                        Some(b) if b < oidx => b,
+                        _ => oidx,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Insert `fp` under index `idx` into every substring table.
+    fn insert(&mut self, idx: usize, fp: Fingerprint) {
+        let subs = self.substrings(fp);
+        for (table, key) in self.tables.iter_mut().zip(subs) {
+            table.entry(key).or_default().push((fp, idx));
+        }
+    }
+}
+
+/// 128-bit counterpart to `MultiIndexHash`, used when `DedupStrategy::SimHash`
+/// is configured with `fingerprint_bits: 128`. Same pigeonhole argument as
+/// `MultiIndexHash`, just partitioning 128 bits instead of 64 — kept as a
+/// separate struct rather than a generic over both so neither path pays for
+/// the other's bit width.
+struct MultiIndexHash128 {
+    part_widths: Vec<u32>,
+    tables: Vec<HashMap<u128, Vec<(Fingerprint128, usize)>>>,
+    threshold: u32,
+}
+
+impl MultiIndexHash128 {
+    fn new(threshold: u32) -> Self {
+        let m = (threshold as usize + 1).clamp(1, 128);
+        let base = 128 / m as u32;
+        let remainder = 128 % m as u32;
+        let part_widths: Vec<u32> = (0..m as u32)
+            .map(|i| if i < remainder { base + 1 } else { base })
+            .collect();
+
+        Self {
+            tables: vec![HashMap::new(); m],
+            part_widths,
+            threshold,
+        }
+    }
+
+    fn substrings(&self, fp: Fingerprint128) -> Vec<u128> {
+        let mut shift = 0u32;
+        let mut out = Vec::with_capacity(self.part_widths.len());
+        for &width in &self.part_widths {
+            let mask = if width >= 128 { u128::MAX } else { (1u128 << width) - 1 };
+            out.push((fp.0 >> shift) & mask);
+            shift += width;
+        }
+        out
+    }
+
+    fn find_or_insert(&mut self, idx: usize, fp: Fingerprint128) -> Option<usize> {
+        if let Some(found) = self.find(fp) {
+            return Some(found);
+        }
+        self.insert(idx, fp);
+        None
+    }
+
+    fn find(&self, fp: Fingerprint128) -> Option<usize> {
+        let subs = self.substrings(fp);
+        let mut best: Option<usize> = None;
+        for (table, key) in self.tables.iter().zip(&subs) {
+            let Some(bucket) = table.get(key) else { continue };
+            for &(ofp, oidx) in bucket {
+                if fp.is_near_duplicate(ofp, self.threshold) {
+                    best = Some(match best {
+                        Some(b) if b < oidx => b,
+                        _ => oidx,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    fn insert(&mut self, idx: usize, fp: Fingerprint128) {
+        let subs = self.substrings(fp);
+        for (table, key) in self.tables.iter_mut().zip(subs) {
+            table.entry(key).or_default().push((fp, idx));
+        }
+    }
+}
+
+// ─── Count-Min Sketch ───────────────────────────────────────────────────────
+
+/// Fixed-memory, concurrent shingle frequency estimator for
+/// `DedupStrategy::WeightedSimHash`'s Phase 0.
+///
+/// A `HashMap<u64, u32>` frequency table would grow with the number of
+/// unique shingles — unbounded on large, varied corpora. A count-min
+/// sketch trades exactness (it only ever overestimates) for a fixed-size
+/// table: `depth` independent rows, each `width` counters wide, every
+/// shingle hash bumps one counter per row, and the frequency estimate is
+/// the minimum across rows (the row where collisions inflated the count
+/// least). Counters are `AtomicU32` so Phase 0's parallel pass over every
+/// line can bump them without a lock.
+struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    table: Vec<AtomicU32>,
+}
+
+impl CountMinSketch {
+    fn new(width: usize, depth: usize) -> Self {
+        let table = (0..width * depth).map(|_| AtomicU32::new(0)).collect();
+        Self { width, depth, table }
+    }
+
+    /// Derive this row's slot from the shingle's hash by mixing in the row
+    /// index, rather than hashing the shingle `depth` separate times — the
+    /// input hash is already uniform, so a cheap per-row mix is enough to
+    /// decorrelate the rows' collisions.
+    fn slot(&self, hash: u64, row: usize) -> usize {
+        let mixed = hash ^ (row as u64).wrapping_mul(0x9E3779B97F4A7C15);
+        (mixed as usize) % self.width
+    }
+
+    fn increment(&self, hash: u64) {
+        for row in 0..self.depth {
+            let idx = row * self.width + self.slot(hash, row);
+            self.table[idx].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn estimate(&self, hash: u64) -> u32 {
+        (0..self.depth)
+            .map(|row| self.table[row * self.width + self.slot(hash, row)].load(Ordering::Relaxed))
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+/// Sketch dimensions tuned for shingle counts up to the low hundreds of
+/// millions per scan while keeping the table itself a few MB: 16 rows
+/// (depth) keeps the min-of-rows estimate close to exact even under heavy
+/// collision load, at 65536 counters (width) each.
+const SHINGLE_SKETCH_WIDTH: usize = 1 << 16;
+const SHINGLE_SKETCH_DEPTH: usize = 16;
+
 // ─── Strategy ───────────────────────────────────────────────────────────────
 
 /// Deduplication strategy.
 #[derive(Debug, Clone, Copy)]
 pub enum DedupStrategy {
-    /// Exact byte-level match. Fastest, strictest.
-    /// Two lines must hash identically to match.
+    /// Exact byte-level match via a single 64-bit FNV-1a hash. Fastest,
+    /// but hits the birthday bound around a few billion lines — at that
+    /// scale a collision will silently merge two distinct examples.
     Exact,
+    /// Exact byte-level match via BLAKE3's 256-bit digest. Collision-free
+    /// at any realistic dataset size; costs more per-line hashing than
+    /// `Exact`, though BLAKE3's tree-structured, SIMD-parallel compression
+    /// keeps it memory-bandwidth-bound rather than CPU-bound.
+    ExactStrong,
     /// Near-duplicate detection via SimHash.
     /// `threshold` = max Hamming distance (0 = exact hash, 3 = fuzzy, 5 = aggressive).
-    SimHash { threshold: u32 },
+    /// `fingerprint_bits` = 64 or 128; 128 roughly doubles the usable
+    /// threshold resolution, reducing false merges on near-identical-but-
+    /// distinct long documents, at the cost of a wider index and no
+    /// `DedupIndex` support (incremental dedup only works at 64 bits).
+    SimHash { threshold: u32, fingerprint_bits: u32 },
+    /// Near-duplicate detection via SimHash, but with each shingle's ±1
+    /// contribution scaled by an IDF-style weight (`ln(N / freq)`) instead
+    /// of uniform ±1, so high-frequency boilerplate shingles (JSON chat
+    /// scaffolding, common phrases) don't dominate the fingerprint over
+    /// rare, content-bearing ones. `threshold` = max Hamming distance, same
+    /// units as `SimHash`. Requires an extra dataset-wide pass (Phase 0) to
+    /// estimate shingle frequencies before fingerprinting, so it isn't
+    /// supported by `DedupIndex` — those frequencies are corpus-relative
+    /// and wouldn't compare meaningfully against a different batch's.
+    WeightedSimHash { threshold: u32 },
+    /// Meaning-level near-duplicate detection via sentence embeddings.
+    /// `threshold` = min cosine similarity (0.0-1.0) to count as a duplicate.
+    /// Requires an [`Embedder`] set via [`DedupEngine::with_embedder`] —
+    /// without one, `scan` treats every line as unique.
+    Semantic { threshold: f32 },
 }
 
 impl Default for DedupStrategy {
     fn default() -> Self {
-        Self::SimHash { threshold: 3 }
+        Self::SimHash {
+            threshold: 3,
+            fingerprint_bits: 64,
+        }
     }
 }
 
@@ -269,7 +625,14 @@ impl std::fmt::Display for DedupStrategy {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Exact => write!(f, "exact"),
-            Self::SimHash { threshold } => write!(f, "simhash(t={})", threshold),
+            Self::ExactStrong => write!(f, "exact-strong"),
+            Self::SimHash {
+                threshold,
+                fingerprint_bits: 128,
+            } => write!(f, "simhash128(t={})", threshold),
+            Self::SimHash { threshold, .. } => write!(f, "simhash(t={})", threshold),
+            Self::WeightedSimHash { threshold } => write!(f, "weighted-simhash(t={})", threshold),
+            Self::Semantic { threshold } => write!(f, "semantic(t={:.2})", threshold),
         }
     }
 }
@@ -295,6 +658,12 @@ pub struct DedupResult {
     /// Maps each line index to its canonical (first-seen) line index.
     /// For unique lines, `canonical_map[i] == i`.
     pub canonical_map: Vec<usize>,
+    /// Bitmask: bit `i` is set if line `i`'s content matched the blocklist
+    /// configured via `DedupEngine::with_blocklist`. Unset for every line
+    /// when no blocklist is configured.
+    pub flagged: BitMask,
+    /// Number of lines flagged by the blocklist.
+    pub flagged_count: usize,
 }
 
 impl DedupResult {
@@ -326,6 +695,481 @@ impl DedupResult {
     }
 }
 
+// ─── DedupIndex ─────────────────────────────────────────────────────────────
+
+/// Magic bytes identifying a `DedupIndex` file on disk.
+const DEDUP_INDEX_MAGIC: &[u8; 4] = b"CDIX";
+
+/// On-disk format version. Bump and branch in `DedupIndex::open` if the
+/// layout ever changes.
+const DEDUP_INDEX_VERSION: u32 = 1;
+
+/// Fixed header size in bytes: magic(4) + version(4) + strategy_tag(1) +
+/// reserved(3) + shingle_size(4) + param(4) + count(8) + reserved(4).
+const DEDUP_INDEX_HEADER_SIZE: usize = 32;
+
+/// Per-entry record size in bytes: fingerprint (u64 LE) + doc_id (u64 LE).
+const DEDUP_INDEX_RECORD_SIZE: usize = 16;
+
+/// Tags the strategy a `DedupIndex` was built with, so `scan_against`
+/// can refuse to compare incompatible fingerprint spaces (e.g. a SimHash
+/// index against an `Exact` scan).
+fn strategy_tag(strategy: DedupStrategy) -> Result<(u8, u32)> {
+    match strategy {
+        DedupStrategy::Exact => Ok((0, 0)),
+        DedupStrategy::SimHash {
+            threshold,
+            fingerprint_bits: 64,
+        } => Ok((1, threshold)),
+        DedupStrategy::SimHash { fingerprint_bits, .. } => {
+            bail!("DedupIndex only supports 64-bit SimHash fingerprints (got {}-bit; the on-disk record format is a fixed 64-bit fingerprint + 64-bit doc_id)", fingerprint_bits)
+        }
+        DedupStrategy::ExactStrong => {
+            bail!("DedupIndex only supports Exact and SimHash strategies (ExactStrong's 256-bit digest doesn't fit the Fingerprint-keyed on-disk format)")
+        }
+        DedupStrategy::WeightedSimHash { .. } => {
+            bail!("DedupIndex only supports Exact and SimHash strategies (WeightedSimHash's shingle weights are corpus-relative and wouldn't compare meaningfully against a different batch's index)")
+        }
+        DedupStrategy::Semantic { .. } => {
+            bail!("DedupIndex only supports Exact and SimHash strategies (Semantic has no fingerprint to index)")
+        }
+    }
+}
+
+fn strategy_from_tag(tag: u8, param: u32) -> Result<DedupStrategy> {
+    match tag {
+        0 => Ok(DedupStrategy::Exact),
+        1 => Ok(DedupStrategy::SimHash {
+            threshold: param,
+            fingerprint_bits: 64,
+        }),
+        other => bail!("Unknown DedupIndex strategy tag: {}", other),
+    }
+}
+
+/// Backing storage for a loaded `DedupIndex`: either a freshly built index
+/// still in memory, or a memory-mapped file opened zero-copy from disk.
+enum IndexStorage {
+    Owned(Vec<u8>),
+    Mmap(memmap2::Mmap),
+}
+
+impl IndexStorage {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            IndexStorage::Owned(v) => v.as_slice(),
+            IndexStorage::Mmap(m) => m.as_ref(),
+        }
+    }
+}
+
+/// A serialized, memory-mappable index of unique fingerprints from a
+/// previously scanned corpus, keyed for fast lookup against a new batch of
+/// lines — the basis for incremental/streaming dedup across runs.
+///
+/// On-disk layout: a fixed-size header (magic, version, strategy, shingle
+/// size, count) followed by a contiguous array of `(fingerprint: u64,
+/// doc_id: u64)` records sorted ascending by fingerprint, so an exact
+/// lookup is a binary search directly over the mapped bytes with zero
+/// deserialization.
+pub struct DedupIndex {
+    storage: IndexStorage,
+    strategy: DedupStrategy,
+    shingle_size: usize,
+    count: usize,
+}
+
+impl DedupIndex {
+    fn record_at(&self, i: usize) -> (u64, u64) {
+        let bytes = &self.storage.as_bytes()[DEDUP_INDEX_HEADER_SIZE..];
+        let offset = i * DEDUP_INDEX_RECORD_SIZE;
+        let fp = u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("8-byte slice"));
+        let doc_id = u64::from_le_bytes(
+            bytes[offset + 8..offset + 16]
+                .try_into()
+                .expect("8-byte slice"),
+        );
+        (fp, doc_id)
+    }
+
+    /// Binary search for an exact fingerprint match. Returns the stored
+    /// `doc_id` of the first record with that fingerprint, if any.
+    fn find_exact(&self, fingerprint: u64) -> Option<u64> {
+        let (mut lo, mut hi) = (0usize, self.count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (fp, doc_id) = self.record_at(mid);
+            match fp.cmp(&fingerprint) {
+                std::cmp::Ordering::Equal => return Some(doc_id),
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+            }
+        }
+        None
+    }
+
+    /// Rebuild an in-memory `MultiIndexHash` from the stored records, for
+    /// near-duplicate (Hamming-distance) lookups — sorted-by-value order
+    /// doesn't help Hamming proximity, so this is a linear pass over the
+    /// index's `count` unique entries rather than a binary search.
+    fn rebuild_multi_index(&self, threshold: u32) -> MultiIndexHash {
+        let mut index = MultiIndexHash::new(threshold);
+        for i in 0..self.count {
+            let (fp, doc_id) = self.record_at(i);
+            // Insert directly rather than via find_or_insert: every stored
+            // record is already a unique, so there's nothing to compare it
+            // against while seeding the tables.
+            index.insert(doc_id as usize, Fingerprint(fp));
+        }
+        index
+    }
+
+    /// Serialize to `path` in the packed binary format described on
+    /// [`DedupIndex`].
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path.as_ref(), self.storage.as_bytes())
+            .with_context(|| format!("Failed to write dedup index to {}", path.as_ref().display()))
+    }
+
+    /// Open a previously saved index, memory-mapped for zero-copy access.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open dedup index: {}", path.display()))?;
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        if mmap.len() < DEDUP_INDEX_HEADER_SIZE {
+            bail!("Dedup index {} is smaller than its header", path.display());
+        }
+        if &mmap[0..4] != DEDUP_INDEX_MAGIC {
+            bail!("{} is not a dedup index file (bad magic)", path.display());
+        }
+        let version = u32::from_le_bytes(mmap[4..8].try_into().expect("4-byte slice"));
+        if version != DEDUP_INDEX_VERSION {
+            bail!(
+                "Dedup index {} has unsupported version {} (expected {})",
+                path.display(),
+                version,
+                DEDUP_INDEX_VERSION
+            );
+        }
+        let strategy_tag_byte = mmap[8];
+        let shingle_size =
+            u32::from_le_bytes(mmap[12..16].try_into().expect("4-byte slice")) as usize;
+        let param = u32::from_le_bytes(mmap[16..20].try_into().expect("4-byte slice"));
+        let count = u64::from_le_bytes(mmap[20..28].try_into().expect("8-byte slice")) as usize;
+        let strategy = strategy_from_tag(strategy_tag_byte, param)?;
+
+        let expected_len = DEDUP_INDEX_HEADER_SIZE + count * DEDUP_INDEX_RECORD_SIZE;
+        if mmap.len() < expected_len {
+            bail!(
+                "Dedup index {} is truncated: expected at least {} bytes, got {}",
+                path.display(),
+                expected_len,
+                mmap.len()
+            );
+        }
+
+        Ok(Self {
+            storage: IndexStorage::Mmap(mmap),
+            strategy,
+            shingle_size,
+            count,
+        })
+    }
+}
+
+// ─── Semantic Embeddings ────────────────────────────────────────────────────
+
+/// Dimensionality used for `DedupStrategy::Semantic` by default — matches
+/// common small sentence-embedding models (e.g. all-MiniLM-L6-v2).
+pub const SEMANTIC_EMBEDDING_DIM: usize = 384;
+
+/// Number of random hyperplanes used to bucket embeddings for LSH. Each
+/// plane contributes one bit to a line's bucket key, so this also bounds
+/// the key to `u64` (must stay <= 64).
+const SEMANTIC_LSH_PLANES: usize = 16;
+
+/// Produces an embedding vector for a line's extracted text content.
+///
+/// `HttpEmbedder` (below) is the practical default today, since Caret
+/// doesn't bundle model weights. A local `candle`-backed embedder can
+/// implement this same trait later without `DedupEngine` needing to change.
+pub trait Embedder: Send + Sync {
+    /// Embedding dimensionality this embedder produces.
+    fn dim(&self) -> usize;
+
+    /// Embed `text`. The result need not be normalized — `DedupEngine`
+    /// L2-normalizes every vector itself before bucketing/comparing.
+    fn embed(&self, text: &str) -> Result<Vec<f32>>;
+}
+
+/// Calls a configurable HTTP embedding endpoint — e.g. an OpenAI-compatible
+/// `/embeddings` route or a self-hosted sentence-transformers server.
+///
+/// Uses a blocking client because `DedupEngine::scan` is synchronous (it's
+/// invoked directly from `App::toggle_dedup` and off a `spawn_blocking`
+/// thread in `mcp::tool_dedup_scan`), so there's no async runtime available
+/// to drive a non-blocking request here.
+pub struct HttpEmbedder {
+    endpoint: String,
+    dim: usize,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpEmbedder {
+    /// `endpoint` is POSTed `{"input": "<text>"}` and must respond with
+    /// `{"embedding": [f32; dim]}`.
+    pub fn new(endpoint: impl Into<String>, dim: usize) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            dim,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+}
+
+impl Embedder for HttpEmbedder {
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        #[derive(serde::Serialize)]
+        struct EmbedRequest<'a> {
+            input: &'a str,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct EmbedResponse {
+            embedding: Vec<f32>,
+        }
+
+        let response: EmbedResponse = self
+            .client
+            .post(&self.endpoint)
+            .json(&EmbedRequest { input: text })
+            .send()
+            .with_context(|| format!("Embedding request to {} failed", self.endpoint))?
+            .error_for_status()
+            .with_context(|| format!("Embedding endpoint {} returned an error", self.endpoint))?
+            .json()
+            .with_context(|| "Embedding endpoint returned an unexpected response shape")?;
+
+        Ok(response.embedding)
+    }
+}
+
+/// L2-normalize `v` in place. A zero vector is left as-is.
+fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        for x in v.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Cosine similarity between two already-L2-normalized vectors — a plain
+/// dot product once both sides have unit length.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Minimal deterministic PRNG (xorshift64), used only to seed LSH
+/// hyperplanes reproducibly — pulling in `rand` would be overkill for
+/// sixteen vectors of Gaussian-ish noise.
+struct XorShift64(u64);
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        Self(seed | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// A pseudo-random value in roughly [-1.0, 1.0].
+    fn next_signed_unit(&mut self) -> f32 {
+        (self.next_u64() as f64 / u64::MAX as f64) as f32 * 2.0 - 1.0
+    }
+}
+
+/// Random-hyperplane LSH: buckets vectors by the sign pattern of
+/// `SEMANTIC_LSH_PLANES` random projections, so a new vector only needs a
+/// full cosine comparison against lines in its own or a Hamming-adjacent
+/// bucket — turning the semantic pass from O(n²) into roughly O(n) for the
+/// common case where most lines are unique.
+struct RandomHyperplaneLsh {
+    planes: Vec<Vec<f32>>,
+}
+
+impl RandomHyperplaneLsh {
+    /// Deterministically seeded so re-scanning the same dataset buckets
+    /// identically — a prerequisite for the on-disk embedding cache to pay
+    /// off across runs.
+    fn new(dim: usize) -> Self {
+        let mut rng = XorShift64::new(0x5EED_F00D_D00D_5EEDu64);
+        let planes = (0..SEMANTIC_LSH_PLANES)
+            .map(|_| (0..dim).map(|_| rng.next_signed_unit()).collect())
+            .collect();
+        Self { planes }
+    }
+
+    fn bucket_key(&self, v: &[f32]) -> u64 {
+        let mut key = 0u64;
+        for (i, plane) in self.planes.iter().enumerate() {
+            let dot: f32 = plane.iter().zip(v).map(|(p, x)| p * x).sum();
+            if dot >= 0.0 {
+                key |= 1u64 << i;
+            }
+        }
+        key
+    }
+
+    /// `key` itself plus every bucket one bit-flip away — a Hamming-radius-1
+    /// multi-probe so near-threshold vectors that landed in a neighboring
+    /// bucket aren't missed.
+    fn probe_keys(&self, key: u64) -> Vec<u64> {
+        let mut keys = Vec::with_capacity(SEMANTIC_LSH_PLANES + 1);
+        keys.push(key);
+        for i in 0..self.planes.len() {
+            keys.push(key ^ (1u64 << i));
+        }
+        keys
+    }
+}
+
+/// Union-find over line indices, used to collapse semantic near-duplicate
+/// pairs into clusters. The root of each cluster is always its smallest
+/// member, so it lines up with `DedupResult::canonical_map`'s "first-seen
+/// wins" convention.
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return;
+        }
+        if ra < rb {
+            self.parent[rb] = ra;
+        } else {
+            self.parent[ra] = rb;
+        }
+    }
+}
+
+/// On-disk cache of `content hash -> embedding`, so re-scanning a dataset
+/// doesn't re-pay embedding cost — often the dominant cost of a semantic
+/// scan, especially against an HTTP endpoint.
+struct EmbeddingCache {
+    path: std::path::PathBuf,
+    entries: HashMap<u64, Vec<f32>>,
+    dirty: bool,
+}
+
+impl EmbeddingCache {
+    /// Cache lives at `~/.cache/caret/embeddings/<dim>.bin`, alongside the
+    /// HF token lookup in `streaming::resolve_hf_token`.
+    fn open(dim: usize) -> Self {
+        let path = dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("caret")
+            .join("embeddings")
+            .join(format!("{}.bin", dim));
+
+        let entries = Self::load(&path).unwrap_or_default();
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    /// Flat binary format: repeated `(hash: u64 LE, len: u32 LE, len * f32 LE)`
+    /// records — no serde round-trip needed for a cache this simple.
+    fn load(path: &std::path::Path) -> Option<HashMap<u64, Vec<f32>>> {
+        let bytes = std::fs::read(path).ok()?;
+        let mut entries = HashMap::new();
+        let mut offset = 0;
+
+        while offset + 12 <= bytes.len() {
+            let hash = u64::from_le_bytes(bytes[offset..offset + 8].try_into().ok()?);
+            offset += 8;
+            let len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().ok()?) as usize;
+            offset += 4;
+
+            let vector_bytes = bytes.get(offset..offset + len * 4)?;
+            let vector = vector_bytes
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes(c.try_into().expect("chunk of 4 bytes")))
+                .collect();
+            offset += len * 4;
+
+            entries.insert(hash, vector);
+        }
+
+        Some(entries)
+    }
+
+    fn get_or_compute(&mut self, hash: u64, text: &str, embedder: &dyn Embedder) -> Result<Vec<f32>> {
+        if let Some(v) = self.entries.get(&hash) {
+            return Ok(v.clone());
+        }
+
+        let mut v = embedder.embed(text)?;
+        normalize(&mut v);
+        self.entries.insert(hash, v.clone());
+        self.dirty = true;
+        Ok(v)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut bytes = Vec::new();
+        for (hash, vector) in &self.entries {
+            bytes.extend_from_slice(&hash.to_le_bytes());
+            bytes.extend_from_slice(&(vector.len() as u32).to_le_bytes());
+            for x in vector {
+                bytes.extend_from_slice(&x.to_le_bytes());
+            }
+        }
+
+        std::fs::write(&self.path, bytes)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
 // ─── DedupEngine ────────────────────────────────────────────────────────────
 
 /// The deduplication engine.
@@ -342,6 +1186,8 @@ impl DedupResult {
 pub struct DedupEngine {
     hasher: SimHasher,
     strategy: DedupStrategy,
+    embedder: Option<Arc<dyn Embedder>>,
+    blocklist: Option<Arc<AhoCorasick>>,
 }
 
 impl DedupEngine {
@@ -349,6 +1195,8 @@ impl DedupEngine {
         Self {
             hasher: SimHasher::default(),
             strategy,
+            embedder: None,
+            blocklist: None,
         }
     }
 
@@ -357,12 +1205,31 @@ impl DedupEngine {
         self
     }
 
+    /// Set the embedder `DedupStrategy::Semantic` uses. Without one, a
+    /// semantic scan treats every line as unique rather than panicking.
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Flag lines whose extracted text content matches any of `patterns`
+    /// (plain substrings, not regexes) during the Phase 1 fingerprint pass.
+    /// Matching rides the same parallel pass `scan` already runs, so the
+    /// extra cost is one Aho-Corasick scan per line rather than a second
+    /// full traversal of the dataset.
+    pub fn with_blocklist(mut self, patterns: &[String]) -> Result<Self> {
+        let automaton = AhoCorasick::new(patterns).context("building blocklist automaton")?;
+        self.blocklist = Some(Arc::new(automaton));
+        Ok(self)
+    }
+
     /// Scan the dataset and return deduplication results.
     ///
     /// Phase 1 is O(N) parallel — bounded by memory bandwidth, not CPU.
-    /// Phase 2 is O(N) for exact mode, O(N*U) for SimHash where U = unique count.
-    /// Each SimHash comparison is XOR + POPCNT (sub-nanosecond), so U up to
-    /// ~5M is practical on modern hardware.
+    /// Phase 2 is O(N) for exact mode. For SimHash it's near-O(N) via
+    /// `MultiIndexHash` — each fingerprint only checks the candidates from
+    /// a handful of buckets rather than every unique fingerprint seen so
+    /// far, which is what makes billion-line dedup practical.
     pub fn scan(&self, dataset: &Dataset) -> DedupResult {
         let start = std::time::Instant::now();
         let line_count = dataset.line_count();
@@ -377,28 +1244,129 @@ impl DedupEngine {
                 elapsed_us: 0,
                 strategy: self.strategy,
                 canonical_map: Vec::new(),
+                flagged: BitMask::new(0),
+                flagged_count: 0,
             };
         }
 
+        // ── Phase 0: Shingle frequency sketch (WeightedSimHash only) ───
+        // Built in parallel ahead of fingerprinting so Phase 1 can weight
+        // each shingle by `ln(total / freq)` — rare, content-bearing
+        // shingles end up dominating the fingerprint instead of
+        // high-frequency boilerplate. `total` is the shingle count summed
+        // across the whole dataset, not the number of distinct shingles.
+        let weighting: Option<(CountMinSketch, u64)> =
+            if matches!(self.strategy, DedupStrategy::WeightedSimHash { .. }) {
+                let sketch = CountMinSketch::new(SHINGLE_SKETCH_WIDTH, SHINGLE_SKETCH_DEPTH);
+                let total = std::sync::atomic::AtomicU64::new(0);
+                (0..line_count).into_par_iter().for_each(|i| {
+                    let line = dataset.get_line(i).unwrap_or("");
+                    let content = extract_content_bytes(line.as_bytes());
+                    if content.len() < self.hasher.shingle_size {
+                        return;
+                    }
+                    let mut count = 0u64;
+                    for window in content.windows(self.hasher.shingle_size) {
+                        sketch.increment(xxh3_64(window));
+                        count += 1;
+                    }
+                    total.fetch_add(count, Ordering::Relaxed);
+                });
+                Some((sketch, total.load(Ordering::Relaxed).max(1)))
+            } else {
+                None
+            };
+
         // ── Phase 1: Parallel fingerprinting ──────────────────────────
         // Each rayon worker reads directly from the mmap.
-        // No copies, no allocations (except the fingerprint Vec itself).
-        let fingerprints: Vec<Fingerprint> = (0..line_count)
+        // No copies, no allocations (except the fingerprint/digest Vecs).
+        let fp_results: Vec<(Fingerprint, Option<[u8; 32]>, bool, Option<Fingerprint128>)> = (0
+            ..line_count)
             .into_par_iter()
             .map(|i| {
                 let line = dataset.get_line(i).unwrap_or("");
-                match self.strategy {
+                let (fp, digest, content, fp128) = match self.strategy {
                     DedupStrategy::Exact => {
-                        Fingerprint(self.hasher.hash_bytes(line.as_bytes()))
+                        (Fingerprint(self.hasher.hash_bytes(line.as_bytes())), None, None, None)
+                    }
+                    DedupStrategy::ExactStrong => {
+                        // Collision-free 256-bit digest. `fingerprints` still
+                        // gets a (truncated, display-only) 64-bit value so
+                        // every strategy fills it in the same way; the real
+                        // dedup key for this strategy is the full digest.
+                        let digest = *blake3::hash(line.as_bytes()).as_bytes();
+                        let truncated = u64::from_le_bytes(
+                            digest[0..8].try_into().expect("8-byte slice of a 32-byte digest"),
+                        );
+                        (Fingerprint(truncated), Some(digest), None, None)
                     }
-                    DedupStrategy::SimHash { .. } => {
+                    DedupStrategy::SimHash {
+                        fingerprint_bits: 128,
+                        ..
+                    } => {
+                        // Same truncated-display-value convention as
+                        // ExactStrong: the real dedup key for this mode is
+                        // the full 128-bit fingerprint, carried separately.
                         let content = extract_content_bytes(line.as_bytes());
-                        self.hasher.fingerprint(&content)
+                        let fp128 = self.hasher.fingerprint128(&content);
+                        (Fingerprint(fp128.0 as u64), None, Some(content), Some(fp128))
                     }
-                }
+                    // `fingerprints` is kept populated under Semantic too —
+                    // it's a cheap byproduct, and callers (e.g. the sample
+                    // duplicate pairs in `mcp::tool_dedup_scan`) rely on
+                    // every strategy filling it in.
+                    DedupStrategy::SimHash { .. } | DedupStrategy::Semantic { .. } => {
+                        let content = extract_content_bytes(line.as_bytes());
+                        let fp = self.hasher.fingerprint(&content);
+                        (fp, None, Some(content), None)
+                    }
+                    DedupStrategy::WeightedSimHash { .. } => {
+                        let content = extract_content_bytes(line.as_bytes());
+                        let (sketch, total) = weighting
+                            .as_ref()
+                            .expect("weighting is built in Phase 0 whenever strategy is WeightedSimHash");
+                        let fp = self.hasher.fingerprint_weighted(&content, |hash| {
+                            let freq = sketch.estimate(hash).max(1) as f64;
+                            (*total as f64 / freq).ln().max(0.0)
+                        });
+                        (fp, None, Some(content), None)
+                    }
+                };
+
+                // Blocklist check reuses the content already extracted for
+                // SimHash/Semantic above; Exact/ExactStrong don't otherwise
+                // extract content, so this is the one extra pass those two
+                // strategies pay when a blocklist is actually configured.
+                let flagged = match &self.blocklist {
+                    Some(automaton) => {
+                        let owned;
+                        let content_bytes = match &content {
+                            Some(c) => c.as_slice(),
+                            None => {
+                                owned = extract_content_bytes(line.as_bytes());
+                                owned.as_slice()
+                            }
+                        };
+                        automaton.is_match(content_bytes)
+                    }
+                    None => false,
+                };
+
+                (fp, digest, flagged, fp128)
             })
             .collect();
 
+        let fingerprints: Vec<Fingerprint> = fp_results.iter().map(|r| r.0).collect();
+        let strong_digests: Vec<Option<[u8; 32]>> = fp_results.iter().map(|r| r.1).collect();
+        let mut flagged = BitMask::new(line_count);
+        for (i, r) in fp_results.iter().enumerate() {
+            if r.2 {
+                flagged.set(i);
+            }
+        }
+        let flagged_count = flagged.count_ones();
+        let fp128s: Vec<Option<Fingerprint128>> = fp_results.iter().map(|r| r.3).collect();
+
         // ── Phase 2: Build dedup index ────────────────────────────────
         // Sequential to preserve first-seen ordering (the first occurrence
         // of a duplicate group is always kept, never flagged).
@@ -423,31 +1391,67 @@ impl DedupEngine {
                     }
                 }
             }
-            DedupStrategy::SimHash { threshold } => {
-                // O(N * U) where U = unique count.
-                // Each comparison is XOR + POPCNT (sub-nanosecond), so
-                // this is practical for U up to ~5M on modern hardware.
-                // For larger datasets, multi-probe LSH is the next step.
-                let mut unique_fps: Vec<(usize, Fingerprint)> =
-                    Vec::with_capacity(line_count);
-
-                for (i, &fp) in fingerprints.iter().enumerate() {
-                    let mut found = false;
+            DedupStrategy::ExactStrong => {
+                // O(N) average with HashMap, same shape as Exact but keyed
+                // by the full 32-byte digest so there's no birthday bound
+                // to worry about at any realistic dataset size.
+                let mut seen: HashMap<[u8; 32], usize> =
+                    HashMap::with_capacity(line_count / 2);
 
-                    for &(canonical_idx, ufp) in &unique_fps {
-                        if fp.is_near_duplicate(ufp, threshold) {
+                for (i, digest) in strong_digests.iter().enumerate() {
+                    let Some(digest) = digest else { continue };
+                    match seen.entry(*digest) {
+                        std::collections::hash_map::Entry::Occupied(e) => {
                             duplicates.set(i);
-                            canonical_map[i] = canonical_idx;
-                            found = true;
-                            break;
+                            canonical_map[i] = *e.get();
+                        }
+                        std::collections::hash_map::Entry::Vacant(e) => {
+                            e.insert(i);
                         }
                     }
+                }
+            }
+            DedupStrategy::SimHash {
+                threshold,
+                fingerprint_bits: 128,
+            } => {
+                // Same multi-index-hashing approach as the 64-bit path,
+                // just over `MultiIndexHash128`'s wider partitions.
+                let mut index = MultiIndexHash128::new(threshold);
+
+                for (i, fp128) in fp128s.iter().enumerate() {
+                    let fp128 = fp128
+                        .expect("fingerprint_bits: 128 populates fp128 for every line in Phase 1");
+                    if let Some(canonical_idx) = index.find_or_insert(i, fp128) {
+                        duplicates.set(i);
+                        canonical_map[i] = canonical_idx;
+                    }
+                }
+            }
+            DedupStrategy::SimHash { threshold, .. } | DedupStrategy::WeightedSimHash { threshold } => {
+                // Near-O(N) via multi-index hashing: each fingerprint only
+                // needs to be verified against the union of `m = threshold
+                // + 1` candidate buckets, not every unique fingerprint seen
+                // so far. See `MultiIndexHash` for the pigeonhole argument.
+                // WeightedSimHash shares this path — it only changes how
+                // the 64-bit fingerprint was computed in Phase 1, not how
+                // near-duplicates are looked up afterward.
+                let mut index = MultiIndexHash::new(threshold);
 
-                    if !found {
-                        unique_fps.push((i, fp));
+                for (i, &fp) in fingerprints.iter().enumerate() {
+                    if let Some(canonical_idx) = index.find_or_insert(i, fp) {
+                        duplicates.set(i);
+                        canonical_map[i] = canonical_idx;
                     }
                 }
             }
+            DedupStrategy::Semantic { threshold } => {
+                if let Some(embedder) = self.embedder.clone() {
+                    self.scan_semantic(dataset, embedder, threshold, &mut duplicates, &mut canonical_map);
+                }
+                // No embedder configured — every line stays unique, since
+                // there's nothing to compare embeddings with.
+            }
         }
 
         let duplicate_count = duplicates.count_ones();
@@ -462,6 +1466,252 @@ impl DedupEngine {
             elapsed_us,
             strategy: self.strategy,
             canonical_map,
+            flagged,
+            flagged_count,
+        }
+    }
+
+    /// Fingerprint a single line under this engine's strategy. Only
+    /// `Exact` and `SimHash` are supported — callers must validate the
+    /// strategy (e.g. via `strategy_tag`) before relying on this.
+    fn fingerprint_line(&self, line: &str) -> Fingerprint {
+        match self.strategy {
+            DedupStrategy::Exact => Fingerprint(self.hasher.hash_bytes(line.as_bytes())),
+            DedupStrategy::SimHash { .. } => {
+                let content = extract_content_bytes(line.as_bytes());
+                self.hasher.fingerprint(&content)
+            }
+            DedupStrategy::ExactStrong
+            | DedupStrategy::WeightedSimHash { .. }
+            | DedupStrategy::Semantic { .. } => {
+                unreachable!("fingerprint_line only called for Exact/SimHash, guarded by strategy_tag")
+            }
+        }
+    }
+
+    /// Build a serializable `DedupIndex` of `dataset`'s unique fingerprints,
+    /// for deduplicating a later batch against this one without rescanning
+    /// it (see `DedupIndex` and `scan_against`).
+    ///
+    /// Only `DedupStrategy::Exact` and 64-bit `DedupStrategy::SimHash` are
+    /// supported — `ExactStrong`'s 256-bit digest, 128-bit SimHash and
+    /// `Semantic`'s embeddings don't fit the fingerprint-keyed on-disk format.
+    pub fn build_index(&self, dataset: &Dataset) -> Result<DedupIndex> {
+        let (tag, param) = strategy_tag(self.strategy)?;
+        let line_count = dataset.line_count();
+
+        let fingerprints: Vec<Fingerprint> = (0..line_count)
+            .into_par_iter()
+            .map(|i| self.fingerprint_line(dataset.get_line(i).unwrap_or("")))
+            .collect();
+
+        // Sequential, first-seen order — same rule `scan`'s Phase 2 uses —
+        // so the set of unique fingerprints captured here matches what a
+        // plain `scan` of this dataset would have treated as canonical.
+        let mut unique_records: Vec<(u64, u64)> = Vec::new();
+        match self.strategy {
+            DedupStrategy::Exact => {
+                let mut seen: std::collections::HashSet<u64> =
+                    std::collections::HashSet::with_capacity(line_count);
+                for (i, fp) in fingerprints.iter().enumerate() {
+                    if seen.insert(fp.0) {
+                        unique_records.push((fp.0, i as u64));
+                    }
+                }
+            }
+            DedupStrategy::SimHash { threshold, .. } => {
+                let mut index = MultiIndexHash::new(threshold);
+                for (i, &fp) in fingerprints.iter().enumerate() {
+                    if index.find_or_insert(i, fp).is_none() {
+                        unique_records.push((fp.0, i as u64));
+                    }
+                }
+            }
+            DedupStrategy::ExactStrong | DedupStrategy::WeightedSimHash { .. } | DedupStrategy::Semantic { .. } => {
+                unreachable!("validated above")
+            }
+        }
+
+        unique_records.sort_unstable_by_key(|&(fp, _)| fp);
+
+        let mut bytes =
+            Vec::with_capacity(DEDUP_INDEX_HEADER_SIZE + unique_records.len() * DEDUP_INDEX_RECORD_SIZE);
+        bytes.extend_from_slice(DEDUP_INDEX_MAGIC);
+        bytes.extend_from_slice(&DEDUP_INDEX_VERSION.to_le_bytes());
+        bytes.push(tag);
+        bytes.extend_from_slice(&[0u8; 3]);
+        bytes.extend_from_slice(&(self.hasher.shingle_size as u32).to_le_bytes());
+        bytes.extend_from_slice(&param.to_le_bytes());
+        bytes.extend_from_slice(&(unique_records.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 4]);
+        for &(fp, doc_id) in &unique_records {
+            bytes.extend_from_slice(&fp.to_le_bytes());
+            bytes.extend_from_slice(&doc_id.to_le_bytes());
+        }
+
+        Ok(DedupIndex {
+            storage: IndexStorage::Owned(bytes),
+            strategy: self.strategy,
+            shingle_size: self.hasher.shingle_size,
+            count: unique_records.len(),
+        })
+    }
+
+    /// Deduplicate `dataset` against a previously built `DedupIndex`
+    /// instead of against itself — lines that match an entry in `index`
+    /// are flagged as duplicates, with `canonical_map` holding the
+    /// matched entry's `doc_id` **in the indexed corpus**, not a line
+    /// index within `dataset`. This is what makes incremental/streaming
+    /// dedup practical: a new batch can be checked against everything
+    /// processed so far without rescanning it.
+    ///
+    /// `index` must have been built with the same strategy (and, for
+    /// `SimHash`, the same threshold) as this engine.
+    pub fn scan_against(&self, dataset: &Dataset, index: &DedupIndex) -> Result<DedupResult> {
+        let (my_tag, my_param) = strategy_tag(self.strategy)?;
+        let (idx_tag, idx_param) = strategy_tag(index.strategy)?;
+        if my_tag != idx_tag || my_param != idx_param {
+            bail!(
+                "DedupIndex was built with strategy {}, but this engine uses {}",
+                index.strategy,
+                self.strategy
+            );
+        }
+        if self.hasher.shingle_size != index.shingle_size {
+            bail!(
+                "DedupIndex was built with shingle size {}, but this engine uses {} \
+                 (fingerprints aren't comparable across shingle sizes)",
+                index.shingle_size,
+                self.hasher.shingle_size
+            );
+        }
+
+        let start = std::time::Instant::now();
+        let line_count = dataset.line_count();
+
+        let fingerprints: Vec<Fingerprint> = (0..line_count)
+            .into_par_iter()
+            .map(|i| self.fingerprint_line(dataset.get_line(i).unwrap_or("")))
+            .collect();
+
+        let mut duplicates = BitMask::new(line_count);
+        let mut canonical_map: Vec<usize> = (0..line_count).collect();
+
+        match self.strategy {
+            DedupStrategy::Exact => {
+                for (i, fp) in fingerprints.iter().enumerate() {
+                    if let Some(doc_id) = index.find_exact(fp.0) {
+                        duplicates.set(i);
+                        canonical_map[i] = doc_id as usize;
+                    }
+                }
+            }
+            DedupStrategy::SimHash { threshold, .. } => {
+                let multi_index = index.rebuild_multi_index(threshold);
+                for (i, &fp) in fingerprints.iter().enumerate() {
+                    if let Some(doc_id) = multi_index.find(fp) {
+                        duplicates.set(i);
+                        canonical_map[i] = doc_id;
+                    }
+                }
+            }
+            DedupStrategy::ExactStrong | DedupStrategy::WeightedSimHash { .. } | DedupStrategy::Semantic { .. } => {
+                unreachable!("validated above")
+            }
+        }
+
+        let duplicate_count = duplicates.count_ones();
+        let elapsed_us = start.elapsed().as_micros() as u64;
+
+        Ok(DedupResult {
+            duplicates,
+            fingerprints,
+            total_lines: line_count,
+            unique_count: line_count - duplicate_count,
+            duplicate_count,
+            elapsed_us,
+            strategy: self.strategy,
+            canonical_map,
+            // `scan_against` is purely incremental dedup against a prior
+            // index, not content filtering — blocklist matching only runs
+            // as part of `scan`'s Phase 1 pass.
+            flagged: BitMask::new(line_count),
+            flagged_count: 0,
+        })
+    }
+
+    /// Embed every line's extracted text content, bucket the resulting
+    /// vectors with random-hyperplane LSH, and union-find lines whose
+    /// cosine similarity clears `threshold` into duplicate clusters.
+    ///
+    /// Embedding is parallelized via rayon (the embedder is `Send + Sync`);
+    /// clustering is sequential to preserve first-seen ordering, same as
+    /// the SimHash phase above.
+    fn scan_semantic(
+        &self,
+        dataset: &Dataset,
+        embedder: Arc<dyn Embedder>,
+        threshold: f32,
+        duplicates: &mut BitMask,
+        canonical_map: &mut [usize],
+    ) {
+        let line_count = dataset.line_count();
+        let cache = Mutex::new(EmbeddingCache::open(embedder.dim()));
+
+        let embeddings: Vec<Option<Vec<f32>>> = (0..line_count)
+            .into_par_iter()
+            .map(|i| {
+                let line = dataset.get_line(i).unwrap_or("");
+                if line.trim().is_empty() {
+                    return None;
+                }
+                let content = extract_content_bytes(line.as_bytes());
+                let text = String::from_utf8_lossy(&content);
+                let hash = self.hasher.hash_bytes(text.as_bytes());
+
+                match cache.lock().expect("embedding cache mutex poisoned").get_or_compute(hash, &text, embedder.as_ref()) {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        tracing::warn!("Failed to embed line {}: {}", i, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        if let Err(e) = cache.lock().expect("embedding cache mutex poisoned").flush() {
+            tracing::warn!("Failed to persist embedding cache: {}", e);
+        }
+
+        let lsh = RandomHyperplaneLsh::new(embedder.dim());
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut union_find = UnionFind::new(line_count);
+
+        for (i, embedding) in embeddings.iter().enumerate() {
+            let Some(v) = embedding else { continue };
+            let key = lsh.bucket_key(v);
+
+            for probe in lsh.probe_keys(key) {
+                let Some(candidates) = buckets.get(&probe) else {
+                    continue;
+                };
+                for &j in candidates {
+                    let Some(other) = &embeddings[j] else { continue };
+                    if cosine_similarity(v, other) >= threshold {
+                        union_find.union(i, j);
+                    }
+                }
+            }
+
+            buckets.entry(key).or_default().push(i);
+        }
+
+        for i in 0..line_count {
+            let canonical = union_find.find(i);
+            canonical_map[i] = canonical;
+            if canonical != i {
+                duplicates.set(i);
+            }
         }
     }
 }
@@ -571,9 +1821,261 @@ mod tests {
         assert!(text.contains("What is Rust?"), "Expected nested content in '{}'", text);
     }
 
+    #[test]
+    fn test_multi_index_hash_finds_near_duplicate() {
+        let mut index = MultiIndexHash::new(3);
+        let fingerprints = vec![Fingerprint(0b1010_1010), Fingerprint(0b1010_1011)];
+
+        assert_eq!(index.find_or_insert(0, fingerprints[0]), None);
+        assert_eq!(index.find_or_insert(1, fingerprints[1]), Some(0));
+    }
+
+    #[test]
+    fn test_multi_index_hash_rejects_far_apart() {
+        let mut index = MultiIndexHash::new(2);
+        let fingerprints = vec![Fingerprint(0x0000_0000_0000_0000), Fingerprint(0xFFFF_FFFF_0000_0000)];
+
+        assert_eq!(index.find_or_insert(0, fingerprints[0]), None);
+        assert_eq!(index.find_or_insert(1, fingerprints[1]), None);
+    }
+
+    #[test]
+    fn test_multi_index_hash_exact_threshold_zero() {
+        // threshold = 0 collapses to m = 1 (a single 64-bit substring),
+        // the case a u32 bucket key couldn't represent.
+        let mut index = MultiIndexHash::new(0);
+        let fingerprints = vec![Fingerprint(0xDEADBEEF), Fingerprint(0xDEADBEEF), Fingerprint(0xDEADBEF0)];
+
+        assert_eq!(index.find_or_insert(0, fingerprints[0]), None);
+        assert_eq!(index.find_or_insert(1, fingerprints[1]), Some(0));
+        assert_eq!(index.find_or_insert(2, fingerprints[2]), None);
+    }
+
+    #[test]
+    fn test_dedup_index_save_open_roundtrip() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut corpus_file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(corpus_file, r#"{{"text": "alpha"}}"#)?;
+        writeln!(corpus_file, r#"{{"text": "bravo"}}"#)?;
+        writeln!(corpus_file, r#"{{"text": "charlie"}}"#)?;
+        let corpus = Dataset::open(corpus_file.path())?;
+
+        let engine = DedupEngine::new(DedupStrategy::Exact);
+        let index = engine.build_index(&corpus)?;
+        assert_eq!(index.count, 3);
+
+        let index_file = NamedTempFile::new()?;
+        index.save(index_file.path())?;
+        let reopened = DedupIndex::open(index_file.path())?;
+        assert_eq!(reopened.count, 3);
+
+        let mut batch_file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(batch_file, r#"{{"text": "alpha"}}"#)?; // duplicate of corpus
+        writeln!(batch_file, r#"{{"text": "delta"}}"#)?; // new
+        let batch = Dataset::open(batch_file.path())?;
+
+        let result = engine.scan_against(&batch, &reopened)?;
+        assert!(result.is_duplicate(0));
+        assert!(!result.is_duplicate(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_index_rejects_unsupported_strategy() {
+        let engine = DedupEngine::new(DedupStrategy::ExactStrong);
+        // No dataset needed — strategy validation happens before any scan.
+        let err = strategy_tag(engine.strategy).unwrap_err();
+        assert!(err.to_string().contains("ExactStrong"));
+    }
+
     #[test]
     fn test_dedup_strategy_display() {
         assert_eq!(format!("{}", DedupStrategy::Exact), "exact");
-        assert_eq!(format!("{}", DedupStrategy::SimHash { threshold: 3 }), "simhash(t=3)");
+        assert_eq!(format!("{}", DedupStrategy::ExactStrong), "exact-strong");
+        assert_eq!(
+            format!(
+                "{}",
+                DedupStrategy::SimHash {
+                    threshold: 3,
+                    fingerprint_bits: 64
+                }
+            ),
+            "simhash(t=3)"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                DedupStrategy::SimHash {
+                    threshold: 3,
+                    fingerprint_bits: 128
+                }
+            ),
+            "simhash128(t=3)"
+        );
+    }
+
+    #[test]
+    fn test_blake3_digest_matches_for_identical_lines() {
+        let a = *blake3::hash(b"identical content").as_bytes();
+        let b = *blake3::hash(b"identical content").as_bytes();
+        let c = *blake3::hash(b"different content").as_bytes();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_blocklist_flags_matching_lines() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(file, r#"{{"text": "hello world"}}"#)?;
+        writeln!(file, r#"{{"text": "this contains a banned phrase here"}}"#)?;
+        writeln!(file, r#"{{"text": "nothing to see"}}"#)?;
+        let dataset = Dataset::open(file.path())?;
+
+        let engine = DedupEngine::new(DedupStrategy::Exact)
+            .with_blocklist(&["banned phrase".to_string()])?;
+        let result = engine.scan(&dataset);
+
+        assert_eq!(result.flagged_count, 1);
+        assert!(result.flagged.get(1));
+        assert!(!result.flagged.get(0));
+        assert!(!result.flagged.get(2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_blocklist_flags_nothing() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(file, r#"{{"text": "anything goes"}}"#)?;
+        let dataset = Dataset::open(file.path())?;
+
+        let engine = DedupEngine::new(DedupStrategy::Exact);
+        let result = engine.scan(&dataset);
+
+        assert_eq!(result.flagged_count, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fingerprint128_hamming_distance() {
+        let a = Fingerprint128(0u128);
+        let b = Fingerprint128(0b1011u128);
+        assert_eq!(a.hamming_distance(b), 3);
+        assert!(a.is_near_duplicate(b, 3));
+        assert!(!a.is_near_duplicate(b, 2));
+
+        // A difference confined to the high 64 bits should still count.
+        let c = Fingerprint128(1u128 << 100);
+        assert_eq!(a.hamming_distance(c), 1);
+    }
+
+    #[test]
+    fn test_simhash128_finds_near_duplicate() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(file, r#"{{"text": "the quick brown fox jumps over the lazy dog"}}"#)?;
+        writeln!(file, r#"{{"text": "the quick brown fox jumps over the lazy cat"}}"#)?;
+        writeln!(file, r#"{{"text": "completely unrelated sentence about weather"}}"#)?;
+        let dataset = Dataset::open(file.path())?;
+
+        let engine = DedupEngine::new(DedupStrategy::SimHash {
+            threshold: 20,
+            fingerprint_bits: 128,
+        });
+        let result = engine.scan(&dataset);
+
+        assert!(result.is_duplicate(1));
+        assert_eq!(result.canonical_map[1], 0);
+        assert!(!result.is_duplicate(2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dedup_index_rejects_128bit_simhash() {
+        let engine = DedupEngine::new(DedupStrategy::SimHash {
+            threshold: 3,
+            fingerprint_bits: 128,
+        });
+        let err = strategy_tag(engine.strategy).unwrap_err();
+        assert!(err.to_string().contains("64-bit"));
+    }
+
+    #[test]
+    fn test_count_min_sketch_estimate() {
+        let sketch = CountMinSketch::new(SHINGLE_SKETCH_WIDTH, SHINGLE_SKETCH_DEPTH);
+        assert_eq!(sketch.estimate(42), 0);
+        sketch.increment(42);
+        sketch.increment(42);
+        sketch.increment(7);
+        assert_eq!(sketch.estimate(42), 2);
+        assert_eq!(sketch.estimate(7), 1);
+    }
+
+    #[test]
+    fn test_fingerprint_weighted_zero_weight_shingle_is_ignored() {
+        let hasher = SimHasher::new(4);
+        let data = b"the quick brown fox jumps over the lazy dog";
+        let unweighted = hasher.fingerprint_weighted(data, |_| 1.0);
+        assert_eq!(unweighted, hasher.fingerprint(data));
+
+        // A shingle weighted to zero can't move any accumulator, so
+        // zero-weighting every shingle should fall back to the all-negative
+        // (all-zero-bit) fingerprint rather than whatever the unweighted
+        // fingerprint happened to be.
+        let zeroed = hasher.fingerprint_weighted(data, |_| 0.0);
+        assert_eq!(zeroed, Fingerprint(0));
+    }
+
+    #[test]
+    fn test_weighted_simhash_strategy_display_and_rejects_index() {
+        assert_eq!(
+            format!("{}", DedupStrategy::WeightedSimHash { threshold: 5 }),
+            "weighted-simhash(t=5)"
+        );
+
+        let engine = DedupEngine::new(DedupStrategy::WeightedSimHash { threshold: 5 });
+        let err = strategy_tag(engine.strategy).unwrap_err();
+        assert!(err.to_string().contains("corpus-relative"));
+    }
+
+    #[test]
+    fn test_weighted_simhash_downweights_shared_boilerplate() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        // Every line shares the same long boilerplate prefix; only the last
+        // few words differ between the two groups. Unweighted SimHash can
+        // merge the boilerplate-dominated fingerprints across groups at a
+        // loose threshold; WeightedSimHash keeps them apart because the
+        // repeated prefix gets a lower IDF weight than the group-specific words.
+        let boilerplate = "respond with the following structured format please consider all context carefully";
+        let mut file = NamedTempFile::with_suffix(".jsonl")?;
+        writeln!(file, r#"{{"text": "{} alpha bravo charlie"}}"#, boilerplate)?;
+        writeln!(file, r#"{{"text": "{} alpha bravo charlie"}}"#, boilerplate)?;
+        writeln!(file, r#"{{"text": "{} delta echo foxtrot"}}"#, boilerplate)?;
+        writeln!(file, r#"{{"text": "{} delta echo foxtrot"}}"#, boilerplate)?;
+        let dataset = Dataset::open(file.path())?;
+
+        let engine = DedupEngine::new(DedupStrategy::WeightedSimHash { threshold: 3 });
+        let result = engine.scan(&dataset);
+
+        // The true duplicate pairs (0,1) and (2,3) are still found...
+        assert!(result.is_duplicate(1));
+        assert_eq!(result.canonical_map[1], 0);
+        assert!(result.is_duplicate(3));
+        assert_eq!(result.canonical_map[3], 2);
+        // ...but the two groups, which differ only in content words, are not
+        // merged with each other.
+        assert_ne!(result.canonical_map[2], result.canonical_map[0]);
+        Ok(())
     }
 }
@@ -3,10 +3,25 @@
 //! Automatically repairs common issues in LLM training datasets.
 
 use regex::Regex;
+use serde::Serialize;
 use serde_json::{Map, Value};
+use std::ops::Range;
+
+/// How confident a fix is, borrowed from ruff's distinction between
+/// automatically-applied and display-only fixes.
+///
+/// `Safe` fixes are deterministic corrections with no ambiguity (e.g.
+/// trimming whitespace). `Unsafe` fixes rely on a heuristic guess (e.g.
+/// where a missing `</think>` tag should go) and should only be applied
+/// when the caller has opted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Applicability {
+    Unsafe,
+    Safe,
+}
 
 /// Types of fixes that can be applied
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub enum FixType {
     /// Added missing </think> tag
     AddedClosingThinkTag,
@@ -27,6 +42,45 @@ impl FixType {
             FixType::TrimmedWhitespaceBeforeNewlines => "Trimmed whitespace before newlines",
         }
     }
+
+    /// How much this fix can be trusted to apply without a human looking
+    /// at it. Think-tag insertion guesses at a close position
+    /// (`find_think_close_position`), so it's `Unsafe`; the whitespace
+    /// fixes are always correct, so they're `Safe`.
+    pub fn applicability(&self) -> Applicability {
+        match self {
+            FixType::AddedClosingThinkTag | FixType::AddedOpeningThinkTag => Applicability::Unsafe,
+            FixType::RemovedTrailingWhitespace | FixType::TrimmedWhitespaceBeforeNewlines => Applicability::Safe,
+        }
+    }
+
+    /// The rule category this fix type belongs to, for bulk enable/disable
+    /// via `FixerConfig::enable_category`/`disable_category`.
+    pub fn category(&self) -> RuleCategory {
+        match self {
+            FixType::AddedClosingThinkTag | FixType::AddedOpeningThinkTag => RuleCategory::ThinkTags,
+            FixType::RemovedTrailingWhitespace | FixType::TrimmedWhitespaceBeforeNewlines => RuleCategory::Whitespace,
+        }
+    }
+}
+
+/// All `FixType`s a default-constructed `Fixer` knows about, used to build
+/// `FixerConfig::all()` and to resolve category-based selection.
+fn known_fix_types() -> [FixType; 4] {
+    [
+        FixType::RemovedTrailingWhitespace,
+        FixType::TrimmedWhitespaceBeforeNewlines,
+        FixType::AddedClosingThinkTag,
+        FixType::AddedOpeningThinkTag,
+    ]
+}
+
+/// A group of related `FixType`s, for ruff-style bulk rule selection (e.g.
+/// disabling every whitespace rule at once instead of listing each one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum RuleCategory {
+    Whitespace,
+    ThinkTags,
 }
 
 /// Reason why a line was skipped
@@ -36,6 +90,10 @@ pub enum SkipReason {
     InvalidJson(String),
     /// Empty line
     EmptyLine,
+    /// An edit's byte range overlapped one already applied to the same
+    /// string, so it was dropped rather than corrupting the other edit's
+    /// offsets
+    ConflictingFix(FixType),
 }
 
 impl SkipReason {
@@ -43,17 +101,120 @@ impl SkipReason {
         match self {
             SkipReason::InvalidJson(e) => format!("Invalid JSON: {}", e),
             SkipReason::EmptyLine => "Empty line".to_string(),
+            SkipReason::ConflictingFix(fix_type) => {
+                format!("Skipped conflicting fix: {}", fix_type.description())
+            }
+        }
+    }
+}
+
+/// A single text edit: replace the byte range `range` of the original
+/// string with `replacement`. Fixes are collected as edits rather than
+/// applied in place so overlapping edits can be detected up front and
+/// callers can preview byte-accurate diffs before anything is applied.
+#[derive(Debug, Clone)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub replacement: String,
+    pub fix_type: FixType,
+}
+
+/// Apply non-overlapping edits to `s`, backward from the end of the string
+/// so that applying one edit never invalidates another edit's offsets.
+///
+/// Edits below `min_applicability` are never applied - they're returned as
+/// `suggested` so a caller can show what *would* be fixed without baking a
+/// guess into the data. Of the remaining edits, they're sorted by start
+/// offset; if a later edit's range starts before the previous (accepted)
+/// edit's range ends, it overlaps and is dropped, recorded as
+/// `SkipReason::ConflictingFix` rather than applied — the first edit
+/// covering a region always wins.
+///
+/// Returns the edits actually applied (in the order they were accepted),
+/// the ones only suggested because they fell below `min_applicability`,
+/// and the conflicts that were dropped.
+fn apply_edits(
+    s: &mut String,
+    edits: Vec<Edit>,
+    min_applicability: Applicability,
+) -> (Vec<Edit>, Vec<Edit>, Vec<SkipReason>) {
+    let (mut eligible, held_back): (Vec<Edit>, Vec<Edit>) = edits
+        .into_iter()
+        .partition(|e| e.fix_type.applicability() >= min_applicability);
+
+    eligible.sort_by_key(|e| (e.range.start, e.range.end));
+
+    let mut accepted: Vec<Edit> = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut last_end = 0usize;
+
+    for edit in eligible {
+        if edit.range.start < last_end {
+            conflicts.push(SkipReason::ConflictingFix(edit.fix_type));
+            continue;
+        }
+        last_end = edit.range.end;
+        accepted.push(edit);
+    }
+
+    // Apply from the end backward: an edit earlier in the string never
+    // needs to know about edits after it, since its own range hasn't moved.
+    for edit in accepted.iter().rev() {
+        s.replace_range(edit.range.clone(), &edit.replacement);
+    }
+
+    (accepted, held_back, conflicts)
+}
+
+/// A machine-readable diagnostic describing one fix, in the spirit of a
+/// rustc/LSP diagnostic: where it applies (a JSON pointer to the field,
+/// plus a byte range within that field's string value), what kind of fix
+/// it is, and the replacement text a consumer could apply itself without
+/// re-running the fixer.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    /// JSON pointer (RFC 6901) to the string field this diagnostic is
+    /// about, e.g. "/messages/2/content"
+    pub path: String,
+    /// Byte range within that field's string value
+    pub range: Range<usize>,
+    pub fix_type: FixType,
+    pub message: String,
+    pub replacement: String,
+}
+
+impl Diagnostic {
+    fn from_edit(path: &str, edit: &Edit) -> Self {
+        Diagnostic {
+            path: path.to_string(),
+            range: edit.range.clone(),
+            fix_type: edit.fix_type.clone(),
+            message: edit.fix_type.description().to_string(),
+            replacement: edit.replacement.clone(),
         }
     }
 }
 
+/// Escape a single JSON Pointer (RFC 6901) segment: `~` -> `~0`, `/` -> `~1`.
+fn escape_json_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
 /// Result of attempting to fix a line
 #[derive(Debug)]
 pub enum FixResult {
-    /// Line was fixed, contains the fixed JSON string and list of fixes applied
+    /// Line was fixed, contains the fixed JSON string, the fixes that were
+    /// applied, the `Unsafe` fixes that were held back below the fixer's
+    /// `min_applicability` (and so only suggested), and any conflicting
+    /// edits that were dropped
     Fixed {
         line: String,
-        fixes: Vec<FixType>,
+        applied: Vec<FixType>,
+        suggested: Vec<FixType>,
+        conflicts: Vec<SkipReason>,
+        /// One diagnostic per edit considered (applied or suggested),
+        /// pointing at the JSON field it came from
+        diagnostics: Vec<Diagnostic>,
     },
     /// Line was already valid, no fixes needed
     Unchanged(String),
@@ -61,11 +222,373 @@ pub enum FixResult {
     Skipped(SkipReason),
 }
 
+impl FixResult {
+    /// Render a line-oriented diff between `original` and this result's
+    /// fixed content, unified-diff style: `-` for a removed line, `+` for
+    /// an added one, two spaces for context. A removed/added pair that
+    /// differs only in trailing whitespace gets that whitespace rendered
+    /// as `·` with a note, since it's otherwise invisible on screen.
+    ///
+    /// Returns an empty string for `Unchanged`/`Skipped`, since there's
+    /// nothing to diff.
+    pub fn diff(&self, original: &str) -> String {
+        match self {
+            FixResult::Fixed { line, .. } => {
+                let orig_lines: Vec<&str> = original.split('\n').collect();
+                let fixed_lines: Vec<&str> = line.split('\n').collect();
+                render_diff(&lcs_diff(&orig_lines, &fixed_lines))
+            }
+            FixResult::Unchanged(_) | FixResult::Skipped(_) => String::new(),
+        }
+    }
+
+    /// Machine-readable diagnostics for this result, for editors/CI to
+    /// render squiggles or selectively apply suggestions without
+    /// re-running the fixer. Empty for `Unchanged`/`Skipped`.
+    pub fn to_diagnostics(&self) -> Vec<Diagnostic> {
+        match self {
+            FixResult::Fixed { diagnostics, .. } => diagnostics.clone(),
+            FixResult::Unchanged(_) | FixResult::Skipped(_) => Vec::new(),
+        }
+    }
+}
+
+/// One line-level diff operation, as produced by `lcs_diff`.
+#[derive(Debug, Clone, PartialEq)]
+enum DiffOp<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Classic dynamic-programming LCS diff: keep the longest run of lines
+/// common to both sides as context, and everything else is a removal from
+/// `a` or an addition from `b`.
+fn lcs_diff<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let n = a.len();
+    let m = b.len();
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            ops.push(DiffOp::Same(a[i]));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            ops.push(DiffOp::Removed(a[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added(b[j]));
+            j += 1;
+        }
+    }
+    while i < n {
+        ops.push(DiffOp::Removed(a[i]));
+        i += 1;
+    }
+    while j < m {
+        ops.push(DiffOp::Added(b[j]));
+        j += 1;
+    }
+    ops
+}
+
+/// Replace a line's trailing run of spaces with `·` so it's visible in a
+/// rendered diff instead of blending into the terminal background.
+fn mark_trailing_whitespace(line: &str) -> String {
+    let trimmed = line.trim_end_matches(' ');
+    let trailing = line.len() - trimmed.len();
+    format!("{}{}", trimmed, "\u{b7}".repeat(trailing))
+}
+
+/// Render diff ops as unified-diff-style text, with a whitespace-only note
+/// when a removed/added pair differs solely in trailing spaces.
+fn render_diff(ops: &[DiffOp]) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+    while i < ops.len() {
+        match &ops[i] {
+            DiffOp::Same(line) => {
+                out.push_str("  ");
+                out.push_str(line);
+                out.push('\n');
+                i += 1;
+            }
+            DiffOp::Removed(old) => {
+                if let Some(DiffOp::Added(new)) = ops.get(i + 1) {
+                    if old != new && old.trim_end_matches(' ') == new.trim_end_matches(' ') {
+                        out.push_str(&format!("- {}\n", mark_trailing_whitespace(old)));
+                        out.push_str(&format!("+ {}\n", mark_trailing_whitespace(new)));
+                        out.push_str("  (whitespace-only change)\n");
+                        i += 2;
+                        continue;
+                    }
+                }
+                out.push_str(&format!("- {}\n", old));
+                i += 1;
+            }
+            DiffOp::Added(new) => {
+                out.push_str(&format!("+ {}\n", new));
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// What a `Rule` inspects to decide which edits, if any, it wants to make
+/// to a string value.
+pub struct RuleContext<'a> {
+    /// The string value under consideration.
+    pub value: &'a str,
+    /// Whether `value` is the `content` field of a `role: "assistant"`
+    /// message object - the think-tag rules only fire here.
+    pub is_assistant_content: bool,
+}
+
+/// One self-contained fix rule. Implementing this - rather than adding a
+/// branch to `Fixer::fix_value`/`fix_object` - is how new rules (normalizing
+/// smart quotes, collapsing blank-line runs, stripping stray BOMs) get
+/// added without touching the central recursion.
+pub trait Rule: Send + Sync {
+    /// Which `FixType` this rule produces - used for `FixerConfig`
+    /// enable/disable selection and for `FixSummary` reporting.
+    fn fix_type(&self) -> FixType;
+
+    /// Collect candidate edits for `ctx`, or an empty `Vec` if this rule
+    /// doesn't apply.
+    fn collect_edits(&self, ctx: &RuleContext) -> Vec<Edit>;
+}
+
+/// Trims trailing whitespace from a string.
+struct TrailingWhitespaceRule;
+
+impl Rule for TrailingWhitespaceRule {
+    fn fix_type(&self) -> FixType {
+        FixType::RemovedTrailingWhitespace
+    }
+
+    fn collect_edits(&self, ctx: &RuleContext) -> Vec<Edit> {
+        let s = ctx.value;
+        let trimmed_len = s.trim_end().len();
+        if trimmed_len < s.len() {
+            vec![Edit {
+                range: trimmed_len..s.len(),
+                replacement: String::new(),
+                fix_type: FixType::RemovedTrailingWhitespace,
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Collapses runs of spaces immediately before a newline down to the
+/// newline alone.
+struct WhitespaceBeforeNewlineRule {
+    regex: Regex,
+}
+
+impl Rule for WhitespaceBeforeNewlineRule {
+    fn fix_type(&self) -> FixType {
+        FixType::TrimmedWhitespaceBeforeNewlines
+    }
+
+    fn collect_edits(&self, ctx: &RuleContext) -> Vec<Edit> {
+        self.regex
+            .find_iter(ctx.value)
+            .map(|m| Edit {
+                range: m.start()..m.end(),
+                replacement: "\n".to_string(),
+                fix_type: FixType::TrimmedWhitespaceBeforeNewlines,
+            })
+            .collect()
+    }
+}
+
+/// Adds a missing `</think>` tag to assistant content that opens more
+/// `<think>` tags than it closes.
+struct ThinkTagCloseRule {
+    open_regex: Regex,
+    close_regex: Regex,
+}
+
+impl Rule for ThinkTagCloseRule {
+    fn fix_type(&self) -> FixType {
+        FixType::AddedClosingThinkTag
+    }
+
+    fn collect_edits(&self, ctx: &RuleContext) -> Vec<Edit> {
+        if !ctx.is_assistant_content {
+            return Vec::new();
+        }
+
+        let s = ctx.value;
+        let open_count = self.open_regex.find_iter(s).count();
+        let close_count = self.close_regex.find_iter(s).count();
+        if open_count <= close_count {
+            return Vec::new();
+        }
+
+        // Missing closing tags - if no </think> follows the last <think>, insert one after
+        // it, using a heuristic break point (a natural paragraph break, or the end of the
+        // content) for where the thinking ends.
+        //
+        // The loop runs once per surplus <think>, but every pass searches a working copy
+        // from the right: once the last <think> is closed, later passes find that same tag
+        // followed by a </think> and insert nothing. So at most one </think> is added, and
+        // an earlier unclosed <think> stays unclosed. That single insertion point is a valid
+        // offset into the untouched original string - no translation is needed.
+        let mut edits = Vec::new();
+        let mut working = s.to_string();
+        for _ in 0..(open_count - close_count) {
+            if let Some(last_open_pos) = working.rfind("<think>") {
+                let after_open = &working[last_open_pos..];
+                if !after_open.contains("</think>") {
+                    let close_pos = find_think_close_position(&working[last_open_pos + 7..]);
+                    let insert_pos = last_open_pos + 7 + close_pos;
+                    working.insert_str(insert_pos, "</think>");
+                    edits.push(Edit {
+                        range: insert_pos..insert_pos,
+                        replacement: "</think>".to_string(),
+                        fix_type: FixType::AddedClosingThinkTag,
+                    });
+                }
+            }
+        }
+        edits
+    }
+}
+
+/// Adds a missing `<think>` tag to assistant content that closes more
+/// `<think>` tags than it opens.
+struct ThinkTagOpenRule {
+    open_regex: Regex,
+    close_regex: Regex,
+}
+
+impl Rule for ThinkTagOpenRule {
+    fn fix_type(&self) -> FixType {
+        FixType::AddedOpeningThinkTag
+    }
+
+    fn collect_edits(&self, ctx: &RuleContext) -> Vec<Edit> {
+        if !ctx.is_assistant_content {
+            return Vec::new();
+        }
+
+        let s = ctx.value;
+        let open_count = self.open_regex.find_iter(s).count();
+        let close_count = self.close_regex.find_iter(s).count();
+        if close_count <= open_count {
+            return Vec::new();
+        }
+
+        // Missing opening tags - prepend <think> for each unmatched </think>
+        (0..(close_count - open_count))
+            .map(|_| Edit {
+                range: 0..0,
+                replacement: "<think>".to_string(),
+                fix_type: FixType::AddedOpeningThinkTag,
+            })
+            .collect()
+    }
+}
+
+/// The default rule set: trailing-whitespace trimming, whitespace-before-
+/// newline collapsing, and think-tag balancing (close then open).
+fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(TrailingWhitespaceRule),
+        Box::new(WhitespaceBeforeNewlineRule {
+            regex: Regex::new(r" +\n").expect("valid regex: whitespace before newline"),
+        }),
+        Box::new(ThinkTagCloseRule {
+            open_regex: Regex::new(r"<think>").expect("valid regex: <think>"),
+            close_regex: Regex::new(r"</think>").expect("valid regex: </think>"),
+        }),
+        Box::new(ThinkTagOpenRule {
+            open_regex: Regex::new(r"<think>").expect("valid regex: <think>"),
+            close_regex: Regex::new(r"</think>").expect("valid regex: </think>"),
+        }),
+    ]
+}
+
+/// Selects which `FixType`s a `Fixer` runs, ruff-style: start from a fixed
+/// set (`all` or `none`) and enable/disable individual types or whole
+/// categories from there.
+#[derive(Debug, Clone)]
+pub struct FixerConfig {
+    enabled: std::collections::HashSet<FixType>,
+}
+
+impl Default for FixerConfig {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FixerConfig {
+    /// Every known rule enabled - the historical `Fixer::new()` behavior.
+    pub fn all() -> Self {
+        Self { enabled: known_fix_types().into_iter().collect() }
+    }
+
+    /// No rules enabled - build up a custom set with `enable`/`enable_category`.
+    pub fn none() -> Self {
+        Self { enabled: std::collections::HashSet::new() }
+    }
+
+    /// Enable a single fix type.
+    pub fn enable(mut self, fix_type: FixType) -> Self {
+        self.enabled.insert(fix_type);
+        self
+    }
+
+    /// Disable a single fix type.
+    pub fn disable(mut self, fix_type: FixType) -> Self {
+        self.enabled.remove(&fix_type);
+        self
+    }
+
+    /// Enable every fix type in `category`.
+    pub fn enable_category(mut self, category: RuleCategory) -> Self {
+        for fix_type in known_fix_types() {
+            if fix_type.category() == category {
+                self.enabled.insert(fix_type);
+            }
+        }
+        self
+    }
+
+    /// Disable every fix type in `category`.
+    pub fn disable_category(mut self, category: RuleCategory) -> Self {
+        self.enabled.retain(|fix_type| fix_type.category() != category);
+        self
+    }
+
+    fn is_enabled(&self, fix_type: &FixType) -> bool {
+        self.enabled.contains(fix_type)
+    }
+}
+
 /// Fixer for reasoning datasets
 pub struct Fixer {
-    think_open_regex: Regex,
-    think_close_regex: Regex,
-    whitespace_before_newline: Regex,
+    rules: Vec<Box<dyn Rule>>,
+    config: FixerConfig,
+    /// Fixes below this applicability are only suggested, never applied.
+    min_applicability: Applicability,
 }
 
 impl Default for Fixer {
@@ -75,19 +598,47 @@ impl Default for Fixer {
 }
 
 impl Fixer {
-    /// Create a new fixer
+    /// Create a new fixer with every rule registered and enabled. Only
+    /// applies `Safe` fixes by default - use `with_min_applicability` to
+    /// also apply `Unsafe` ones such as guessed think-tag positions, and
+    /// `with_config` to enable/disable individual rules or categories.
     pub fn new() -> Self {
         Self {
-            think_open_regex: Regex::new(r"<think>").expect("valid regex: <think>"),
-            think_close_regex: Regex::new(r"</think>").expect("valid regex: </think>"),
-            whitespace_before_newline: Regex::new(r" +\n").expect("valid regex: whitespace before newline"),
+            rules: default_rules(),
+            config: FixerConfig::all(),
+            min_applicability: Applicability::Safe,
         }
     }
 
+    /// Set the minimum applicability a fix needs to be applied rather than
+    /// just suggested. Pass `Applicability::Unsafe` to also bake in
+    /// heuristic fixes like think-tag insertion.
+    pub fn with_min_applicability(mut self, min_applicability: Applicability) -> Self {
+        self.min_applicability = min_applicability;
+        self
+    }
+
+    /// Restrict which rules run, ruff-style - see `FixerConfig`.
+    pub fn with_config(mut self, config: FixerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The `FixType`s this fixer will actually run on the next `fix_line`
+    /// call, after `FixerConfig` selection - what `FixSummary::set_active_rules`
+    /// records for a run.
+    pub fn active_rules(&self) -> Vec<FixType> {
+        self.rules
+            .iter()
+            .map(|rule| rule.fix_type())
+            .filter(|fix_type| self.config.is_enabled(fix_type))
+            .collect()
+    }
+
     /// Fix a single line of JSONL
     pub fn fix_line(&self, line: &str) -> FixResult {
         let trimmed = line.trim();
-        
+
         // Skip empty lines
         if trimmed.is_empty() {
             return FixResult::Skipped(SkipReason::EmptyLine);
@@ -99,44 +650,78 @@ impl Fixer {
             Err(e) => return FixResult::Skipped(SkipReason::InvalidJson(e.to_string())),
         };
 
-        let mut fixes = Vec::new();
+        let mut applied = Vec::new();
+        let mut suggested = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut diagnostics = Vec::new();
 
-        // Fix the JSON value in place
-        self.fix_value(&mut json_value, &mut fixes);
+        // Fix the JSON value in place, tracking our position as a JSON
+        // pointer (RFC 6901) so diagnostics can point back at the field
+        // they came from.
+        self.fix_value(&mut json_value, "", false, &mut applied, &mut suggested, &mut conflicts, &mut diagnostics);
 
         // Serialize back to JSON
         let fixed_line = serde_json::to_string(&json_value).expect("parsed JSON should be re-serializable");
 
-        if fixes.is_empty() {
+        if applied.is_empty() && suggested.is_empty() {
             FixResult::Unchanged(fixed_line)
         } else {
             FixResult::Fixed {
                 line: fixed_line,
-                fixes,
+                applied,
+                suggested,
+                conflicts,
+                diagnostics,
             }
         }
     }
 
-    /// Recursively fix a JSON value
-    fn fix_value(&self, value: &mut Value, fixes: &mut Vec<FixType>) {
+    /// Recursively fix a JSON value. `path` is the JSON pointer to `value`
+    /// itself, built up as the recursion descends into arrays/objects.
+    /// `is_assistant_content` is set by the parent object exactly when
+    /// `value` is the `content` field of a `role: "assistant"` message -
+    /// everywhere else it's `false`, so think-tag rules self-exclude via
+    /// `RuleContext` instead of `fix_object` branching per rule.
+    #[allow(clippy::too_many_arguments)]
+    fn fix_value(
+        &self,
+        value: &mut Value,
+        path: &str,
+        is_assistant_content: bool,
+        applied: &mut Vec<FixType>,
+        suggested: &mut Vec<FixType>,
+        conflicts: &mut Vec<SkipReason>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
         match value {
             Value::String(s) => {
-                self.fix_string(s, fixes);
+                let ctx = RuleContext { value: s, is_assistant_content };
+                let edits = self.collect_rule_edits(&ctx);
+                self.apply_and_record(s, edits, path, applied, suggested, conflicts, diagnostics);
             }
             Value::Array(arr) => {
-                for item in arr.iter_mut() {
-                    self.fix_value(item, fixes);
+                for (i, item) in arr.iter_mut().enumerate() {
+                    let item_path = format!("{path}/{i}");
+                    self.fix_value(item, &item_path, false, applied, suggested, conflicts, diagnostics);
                 }
             }
             Value::Object(obj) => {
-                self.fix_object(obj, fixes);
+                self.fix_object(obj, path, applied, suggested, conflicts, diagnostics);
             }
             _ => {}
         }
     }
 
     /// Fix a JSON object, with special handling for message objects
-    fn fix_object(&self, obj: &mut Map<String, Value>, fixes: &mut Vec<FixType>) {
+    fn fix_object(
+        &self,
+        obj: &mut Map<String, Value>,
+        path: &str,
+        applied: &mut Vec<FixType>,
+        suggested: &mut Vec<FixType>,
+        conflicts: &mut Vec<SkipReason>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
         // Check if this is a message object with role=assistant
         let is_assistant = obj
             .get("role")
@@ -145,84 +730,55 @@ impl Fixer {
             .unwrap_or(false);
 
         for (key, value) in obj.iter_mut() {
-            // For assistant messages, apply think tag fixes to content
-            if is_assistant && key == "content" {
-                if let Value::String(s) = value {
-                    self.fix_string(s, fixes);
-                    self.fix_think_tags(s, fixes);
-                }
-            } else {
-                self.fix_value(value, fixes);
-            }
+            let field_path = format!("{path}/{}", escape_json_pointer_segment(key));
+            let is_assistant_content = is_assistant && key == "content";
+            self.fix_value(value, &field_path, is_assistant_content, applied, suggested, conflicts, diagnostics);
         }
     }
 
-    /// Fix common string issues (whitespace)
-    fn fix_string(&self, s: &mut String, fixes: &mut Vec<FixType>) {
-        // Fix trailing whitespace
-        let original_len = s.len();
-        let trimmed = s.trim_end().to_string();
-        if trimmed.len() < original_len {
-            *s = trimmed;
-            if !fixes.contains(&FixType::RemovedTrailingWhitespace) {
-                fixes.push(FixType::RemovedTrailingWhitespace);
-            }
-        }
-
-        // Fix whitespace before newlines
-        if self.whitespace_before_newline.is_match(s) {
-            *s = self.whitespace_before_newline.replace_all(s, "\n").to_string();
-            if !fixes.contains(&FixType::TrimmedWhitespaceBeforeNewlines) {
-                fixes.push(FixType::TrimmedWhitespaceBeforeNewlines);
+    /// Run every enabled rule against `ctx`, collecting all the edits they
+    /// propose. Since rule order only matters within `apply_edits`'s own
+    /// conflict resolution (by byte offset, not registration order), the
+    /// active rules can be iterated in any order.
+    fn collect_rule_edits(&self, ctx: &RuleContext) -> Vec<Edit> {
+        let mut edits = Vec::new();
+        for rule in &self.rules {
+            if self.config.is_enabled(&rule.fix_type()) {
+                edits.extend(rule.collect_edits(ctx));
             }
         }
+        edits
     }
 
-    /// Fix unbalanced think tags
-    fn fix_think_tags(&self, s: &mut String, fixes: &mut Vec<FixType>) {
-        let open_count = self.think_open_regex.find_iter(s).count();
-        let close_count = self.think_close_regex.find_iter(s).count();
-
-        match open_count.cmp(&close_count) {
-            std::cmp::Ordering::Greater => {
-                // Missing closing tags - find where each <think> ends and add </think> if missing
-                // Simple approach: add missing </think> tags at the end of each unclosed section
-                for _ in 0..(open_count - close_count) {
-                    // Find the last <think> that doesn't have a matching </think>
-                    // For simplicity, append </think> right after the last unclosed <think>'s content
-                    // A smarter approach would find where the thinking ends, but we'll use a heuristic:
-                    // Insert </think> before the final answer (after all thinking is done)
-                    
-                    if let Some(last_open_pos) = s.rfind("<think>") {
-                        // Check if there's a </think> after this position
-                        let after_open = &s[last_open_pos..];
-                        if !after_open.contains("</think>") {
-                            // No closing tag after this opening - add one
-                            // Try to find a natural break point (end of thinking)
-                            // If the content has a clear answer section, insert before it
-                            // Otherwise, look for patterns like double newlines
-                            let close_pos = find_think_close_position(&s[last_open_pos + 7..]);
-                            let insert_pos = last_open_pos + 7 + close_pos;
-                            s.insert_str(insert_pos, "</think>");
-                            if !fixes.contains(&FixType::AddedClosingThinkTag) {
-                                fixes.push(FixType::AddedClosingThinkTag);
-                            }
-                        }
-                    }
-                }
+    /// Apply `edits` to `s`, merging the resulting applied/suggested fixes
+    /// and conflicts into the caller's accumulators (deduping fix types,
+    /// the same way the old in-place mutators did), and recording one
+    /// `Diagnostic` per edit considered against `path`.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_and_record(
+        &self,
+        s: &mut String,
+        edits: Vec<Edit>,
+        path: &str,
+        applied: &mut Vec<FixType>,
+        suggested: &mut Vec<FixType>,
+        conflicts: &mut Vec<SkipReason>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        let (newly_applied, newly_suggested, conflicted) = apply_edits(s, edits, self.min_applicability);
+        for edit in &newly_applied {
+            if !applied.contains(&edit.fix_type) {
+                applied.push(edit.fix_type.clone());
             }
-            std::cmp::Ordering::Less => {
-                // Missing opening tags - prepend <think> for each unmatched </think>
-                for _ in 0..(close_count - open_count) {
-                    // Prepend <think> at the beginning
-                    *s = format!("<think>{}", s);
-                    if !fixes.contains(&FixType::AddedOpeningThinkTag) {
-                        fixes.push(FixType::AddedOpeningThinkTag);
-                    }
-                }
+            diagnostics.push(Diagnostic::from_edit(path, edit));
+        }
+        for edit in &newly_suggested {
+            if !suggested.contains(&edit.fix_type) {
+                suggested.push(edit.fix_type.clone());
             }
-            std::cmp::Ordering::Equal => {}
+            diagnostics.push(Diagnostic::from_edit(path, edit));
         }
+        conflicts.extend(conflicted);
     }
 }
 
@@ -250,6 +806,18 @@ pub struct FixSummary {
     pub unchanged_lines: usize,
     pub skipped_lines: usize,
     pub fixes_by_type: std::collections::HashMap<String, usize>,
+    /// Fixes that were below the fixer's `min_applicability` and so were
+    /// only suggested, never applied
+    pub suggested_by_type: std::collections::HashMap<String, usize>,
+    /// Edits dropped because they overlapped one already applied to the
+    /// same string (see `SkipReason::ConflictingFix`)
+    pub conflicting_fixes: usize,
+    /// Per-line diffs collected via `record_diff` when the caller opts
+    /// into `--diff`-style output, keyed by 1-based line number.
+    pub diffs: Vec<(usize, String)>,
+    /// The rules the `Fixer` had active for this run, set once via
+    /// `set_active_rules` before processing starts.
+    pub active_rules: Vec<FixType>,
 }
 
 impl FixSummary {
@@ -257,12 +825,37 @@ impl FixSummary {
         Self::default()
     }
 
-    pub fn record_fixed(&mut self, fixes: &[FixType]) {
+    /// Record which rules were active for this run, from `Fixer::active_rules`.
+    pub fn set_active_rules(&mut self, active_rules: Vec<FixType>) {
+        self.active_rules = active_rules;
+    }
+
+    /// Record a rendered `FixResult::diff` for batch `--diff` output.
+    pub fn record_diff(&mut self, line_number: usize, diff: String) {
+        self.diffs.push((line_number, diff));
+    }
+
+    /// Render every collected diff as one report, a `line N` header
+    /// followed by that line's diff body.
+    pub fn render_diffs(&self) -> String {
+        let mut out = String::new();
+        for (line_number, diff) in &self.diffs {
+            out.push_str(&format!("--- line {} ---\n", line_number));
+            out.push_str(diff);
+        }
+        out
+    }
+
+    pub fn record_fixed(&mut self, applied: &[FixType], suggested: &[FixType], conflicts: &[SkipReason]) {
         self.total_lines += 1;
         self.fixed_lines += 1;
-        for fix in fixes {
+        for fix in applied {
             *self.fixes_by_type.entry(fix.description().to_string()).or_insert(0) += 1;
         }
+        for fix in suggested {
+            *self.suggested_by_type.entry(fix.description().to_string()).or_insert(0) += 1;
+        }
+        self.conflicting_fixes += conflicts.len();
     }
 
     pub fn record_unchanged(&mut self) {
@@ -284,25 +877,46 @@ mod tests {
     fn test_fix_trailing_whitespace() {
         let fixer = Fixer::new();
         let input = r#"{"content": "hello world   "}"#;
-        
+
         match fixer.fix_line(input) {
-            FixResult::Fixed { line, fixes } => {
+            FixResult::Fixed { line, applied, conflicts, .. } => {
                 assert!(line.contains(r#""hello world""#));
-                assert!(fixes.contains(&FixType::RemovedTrailingWhitespace));
+                assert!(applied.contains(&FixType::RemovedTrailingWhitespace));
+                assert!(conflicts.is_empty());
             }
             _ => panic!("Expected Fixed result"),
         }
     }
 
     #[test]
-    fn test_fix_unclosed_think_tag() {
+    fn test_fix_unclosed_think_tag_is_unsafe_by_default() {
+        // Fixer::new() only applies Safe fixes, so the guessed think-tag
+        // close position is suggested, not baked into the line.
         let fixer = Fixer::new();
         let input = r#"{"messages": [{"role": "assistant", "content": "<think>thinking here"}]}"#;
-        
+
         match fixer.fix_line(input) {
-            FixResult::Fixed { line, fixes } => {
+            FixResult::Fixed { line, applied, suggested, conflicts, .. } => {
+                assert!(!line.contains("</think>"));
+                assert!(applied.is_empty());
+                assert!(suggested.contains(&FixType::AddedClosingThinkTag));
+                assert!(conflicts.is_empty());
+            }
+            _ => panic!("Expected Fixed result"),
+        }
+    }
+
+    #[test]
+    fn test_fix_unclosed_think_tag_applied_when_unsafe_allowed() {
+        let fixer = Fixer::new().with_min_applicability(Applicability::Unsafe);
+        let input = r#"{"messages": [{"role": "assistant", "content": "<think>thinking here"}]}"#;
+
+        match fixer.fix_line(input) {
+            FixResult::Fixed { line, applied, suggested, conflicts, .. } => {
                 assert!(line.contains("</think>"));
-                assert!(fixes.contains(&FixType::AddedClosingThinkTag));
+                assert!(applied.contains(&FixType::AddedClosingThinkTag));
+                assert!(suggested.is_empty());
+                assert!(conflicts.is_empty());
             }
             _ => panic!("Expected Fixed result"),
         }
@@ -333,10 +947,194 @@ mod tests {
     #[test]
     fn test_skip_empty_line() {
         let fixer = Fixer::new();
-        
+
         match fixer.fix_line("") {
             FixResult::Skipped(SkipReason::EmptyLine) => {}
             _ => panic!("Expected Skipped result"),
         }
     }
+
+    #[test]
+    fn test_apply_edits_drops_overlapping_edit() {
+        let mut s = "hello world".to_string();
+        let edits = vec![
+            Edit { range: 5..11, replacement: "!".to_string(), fix_type: FixType::RemovedTrailingWhitespace },
+            Edit {
+                range: 5..8,
+                replacement: "???".to_string(),
+                fix_type: FixType::TrimmedWhitespaceBeforeNewlines,
+            },
+        ];
+
+        let (applied, suggested, conflicts) = apply_edits(&mut s, edits, Applicability::Safe);
+
+        // Sorted by (start, end): the shorter 5..8 edit sorts first and wins;
+        // the 5..11 edit starts before it ends, so it overlaps and is dropped.
+        assert_eq!(s, "hello???rld");
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].fix_type, FixType::TrimmedWhitespaceBeforeNewlines);
+        assert!(suggested.is_empty());
+        assert_eq!(conflicts.len(), 1);
+        assert!(matches!(conflicts[0], SkipReason::ConflictingFix(FixType::RemovedTrailingWhitespace)));
+    }
+
+    #[test]
+    fn test_apply_edits_holds_back_unsafe_edit_as_suggested() {
+        let mut s = "hello world".to_string();
+        let edits = vec![Edit {
+            range: 5..5,
+            replacement: ",".to_string(),
+            fix_type: FixType::AddedClosingThinkTag,
+        }];
+
+        let (applied, suggested, conflicts) = apply_edits(&mut s, edits, Applicability::Safe);
+
+        assert_eq!(s, "hello world");
+        assert!(applied.is_empty());
+        assert_eq!(suggested.len(), 1);
+        assert_eq!(suggested[0].fix_type, FixType::AddedClosingThinkTag);
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_trailing_whitespace_before_newline_conflict_keeps_one_fix() {
+        // "   \n" at the very end is matched by both the trailing-whitespace
+        // trim and the whitespace-before-newline regex - the same bug the
+        // edit model is meant to catch instead of silently corrupting offsets.
+        let fixer = Fixer::new();
+        let input = "{\"content\": \"hello   \\n   \"}";
+
+        match fixer.fix_line(input) {
+            FixResult::Fixed { line, conflicts, .. } => {
+                assert!(!line.contains("   \\n"));
+                assert!(!conflicts.is_empty(), "expected the overlapping whitespace edit to be flagged");
+            }
+            other => panic!("Expected Fixed result, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_diff_marks_trailing_whitespace() {
+        // fix_line re-serializes through serde_json, which also normalizes
+        // key spacing - so exercise diff() directly against a result whose
+        // only line-level difference is trailing whitespace.
+        let result = FixResult::Fixed {
+            line: "hello world".to_string(),
+            applied: vec![FixType::RemovedTrailingWhitespace],
+            suggested: vec![],
+            conflicts: vec![],
+            diagnostics: vec![],
+        };
+
+        let diff = result.diff("hello world   ");
+
+        assert!(diff.contains('\u{b7}'), "expected trailing whitespace to be marked: {diff}");
+        assert!(diff.contains("whitespace-only change"));
+    }
+
+    #[test]
+    fn test_diff_unchanged_is_empty() {
+        let fixer = Fixer::new();
+        let input = r#"{"messages": [{"role": "assistant", "content": "<think>ok</think>answer"}]}"#;
+
+        let result = fixer.fix_line(input);
+        assert_eq!(result.diff(input), "");
+    }
+
+    #[test]
+    fn test_lcs_diff_keeps_common_lines_as_context() {
+        let a = vec!["one", "two", "three"];
+        let b = vec!["one", "two and a half", "three"];
+
+        let ops = lcs_diff(&a, &b);
+        assert_eq!(
+            ops,
+            vec![
+                DiffOp::Same("one"),
+                DiffOp::Removed("two"),
+                DiffOp::Added("two and a half"),
+                DiffOp::Same("three"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_diagnostics_points_at_nested_content_field() {
+        let fixer = Fixer::new();
+        let input = r#"{"messages": [{"role": "user", "content": "hi"}, {"role": "assistant", "content": "hello world   "}]}"#;
+
+        let result = fixer.fix_line(input);
+        let diagnostics = result.to_diagnostics();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "/messages/1/content");
+        assert_eq!(diagnostics[0].fix_type, FixType::RemovedTrailingWhitespace);
+        assert_eq!(diagnostics[0].replacement, "");
+    }
+
+    #[test]
+    fn test_to_diagnostics_includes_suggested_fixes() {
+        let fixer = Fixer::new();
+        let input = r#"{"messages": [{"role": "assistant", "content": "<think>thinking here"}]}"#;
+
+        let result = fixer.fix_line(input);
+        let diagnostics = result.to_diagnostics();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "/messages/0/content");
+        assert_eq!(diagnostics[0].fix_type, FixType::AddedClosingThinkTag);
+    }
+
+    #[test]
+    fn test_escape_json_pointer_segment() {
+        assert_eq!(escape_json_pointer_segment("a/b"), "a~1b");
+        assert_eq!(escape_json_pointer_segment("a~b"), "a~0b");
+    }
+
+    #[test]
+    fn test_disabled_rule_is_not_applied_or_suggested() {
+        let fixer = Fixer::new()
+            .with_min_applicability(Applicability::Unsafe)
+            .with_config(FixerConfig::all().disable(FixType::AddedClosingThinkTag));
+        let input = r#"{"messages": [{"role": "assistant", "content": "<think>thinking here"}]}"#;
+
+        match fixer.fix_line(input) {
+            FixResult::Unchanged(_) => {}
+            other => panic!("Expected Unchanged result with the rule disabled, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_disabled_category_leaves_other_categories_active() {
+        let fixer = Fixer::new().with_config(FixerConfig::all().disable_category(RuleCategory::ThinkTags));
+        let input = r#"{"content": "hello world   "}"#;
+
+        match fixer.fix_line(input) {
+            FixResult::Fixed { line, applied, .. } => {
+                assert!(line.contains(r#""hello world""#));
+                assert!(applied.contains(&FixType::RemovedTrailingWhitespace));
+            }
+            other => panic!("Expected Fixed result, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_config_none_runs_no_rules() {
+        let fixer = Fixer::new()
+            .with_min_applicability(Applicability::Unsafe)
+            .with_config(FixerConfig::none());
+        let input = r#"{"content": "hello world   "}"#;
+
+        match fixer.fix_line(input) {
+            FixResult::Unchanged(_) => {}
+            other => panic!("Expected Unchanged result with no rules enabled, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_active_rules_reflects_config() {
+        let fixer = Fixer::new().with_config(FixerConfig::none().enable(FixType::RemovedTrailingWhitespace));
+        assert_eq!(fixer.active_rules(), vec![FixType::RemovedTrailingWhitespace]);
+    }
 }
+
@@ -2,12 +2,14 @@
 //!
 //! Detects and converts various dataset formats (JSONL, Parquet, CSV) to a common representation.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use arrow::json::LineDelimitedWriter;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Cursor, Write};
 use std::path::Path;
+use std::sync::Arc;
 
 /// Supported input formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,6 +46,25 @@ impl InputFormat {
     }
 }
 
+/// Serialize a single Arrow `RecordBatch` to JSONL strings, skipping blank
+/// lines. Shared by the eager (`parquet_to_jsonl`) and lazy
+/// (`data::ParquetLazyReader`) Parquet decode paths so both stay in sync.
+pub(crate) fn record_batch_to_jsonl_lines(batch: &arrow::record_batch::RecordBatch) -> Result<Vec<String>> {
+    let mut buf = Vec::new();
+    {
+        let mut writer = LineDelimitedWriter::new(&mut buf);
+        writer.write(batch).with_context(|| "Failed to serialize batch to JSON")?;
+        writer.finish().with_context(|| "Failed to finish JSON writer")?;
+    }
+
+    let json_str = String::from_utf8(buf).with_context(|| "Invalid UTF-8 in JSON output")?;
+    Ok(json_str
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.to_string())
+        .collect())
+}
+
 /// Convert a Parquet file to JSONL strings in memory
 pub fn parquet_to_jsonl<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
     let path = path.as_ref();
@@ -60,53 +81,206 @@ pub fn parquet_to_jsonl<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
 
     for batch_result in reader {
         let batch = batch_result.with_context(|| "Failed to read Parquet batch")?;
-        
-        // Convert batch to JSON using Arrow's JSON writer
-        let mut buf = Vec::new();
-        {
-            let mut writer = LineDelimitedWriter::new(&mut buf);
-            writer.write(&batch).with_context(|| "Failed to serialize batch to JSON")?;
-            writer.finish().with_context(|| "Failed to finish JSON writer")?;
+        lines.extend(record_batch_to_jsonl_lines(&batch)?);
+    }
+
+    Ok(lines)
+}
+
+/// Options controlling CSV -> JSONL conversion.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvOptions {
+    /// When true, header names may carry a `:number`/`:boolean`/`:string`
+    /// type suffix (MeiliSearch-style), and columns without a suffix have
+    /// each cell's JSON type inferred individually (see [`infer_cell`]).
+    /// When false (the default), every cell is emitted as a JSON string,
+    /// matching historical behavior.
+    pub typed: bool,
+    /// Delimiter, quoting, comment, and header dialect to parse with.
+    pub dialect: CsvDialect,
+}
+
+/// Parsing dialect for CSV/TSV files: delimiter, quote character, comment
+/// lines, whitespace trimming, and whether the first row is a header.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvDialect {
+    /// Field delimiter byte (`,` for CSV, `\t` for TSV).
+    pub delimiter: u8,
+    /// Quote character byte.
+    pub quote: u8,
+    /// Trim leading/trailing whitespace from every field.
+    pub trim: bool,
+    /// Skip lines starting with this byte (e.g. `b'#'`), if set.
+    pub comment: Option<u8>,
+    /// Whether the first row is a header. When false, columns are named
+    /// `column_0`, `column_1`, ... and the first row is treated as data.
+    pub has_header: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            trim: false,
+            comment: None,
+            has_header: true,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Dialect defaults for `path`, auto-selecting a tab delimiter for
+    /// `.tsv` files so `InputFormat::detect`'s TSV-as-CSV mapping actually
+    /// parses tab-separated files correctly.
+    pub fn for_path<P: AsRef<Path>>(path: P) -> Self {
+        let mut dialect = Self::default();
+        if path.as_ref().extension().and_then(|e| e.to_str()) == Some("tsv") {
+            dialect.delimiter = b'\t';
         }
+        dialect
+    }
+}
 
-        // Split into lines
-        let json_str = String::from_utf8(buf)
-            .with_context(|| "Invalid UTF-8 in JSON output")?;
-        
-        for line in json_str.lines() {
-            if !line.trim().is_empty() {
-                lines.push(line.to_string());
-            }
+/// A column's declared (from a header suffix) or inferred JSON type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Number,
+    Boolean,
+    String,
+    Inferred,
+}
+
+/// Split a header like `price:number` into its name and declared type.
+/// Unrecognized or absent suffixes fall back to per-cell inference.
+fn parse_header(header: &str) -> (String, ColumnType) {
+    if let Some((name, suffix)) = header.rsplit_once(':') {
+        match suffix.to_lowercase().as_str() {
+            "number" => return (name.to_string(), ColumnType::Number),
+            "boolean" => return (name.to_string(), ColumnType::Boolean),
+            "string" => return (name.to_string(), ColumnType::String),
+            _ => {}
         }
     }
+    (header.to_string(), ColumnType::Inferred)
+}
 
-    Ok(lines)
+/// Infer a cell's JSON type when its column has no explicit annotation:
+/// empty -> null, integer/float -> number, true/false (case-insensitive) ->
+/// boolean, otherwise string.
+fn infer_cell(value: &str) -> serde_json::Value {
+    if value.is_empty() {
+        serde_json::Value::Null
+    } else if let Ok(i) = value.parse::<i64>() {
+        serde_json::Value::Number(i.into())
+    } else if let Ok(f) = value.parse::<f64>() {
+        serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::Value::String(value.to_string()))
+    } else if value.eq_ignore_ascii_case("true") {
+        serde_json::Value::Bool(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        serde_json::Value::Bool(false)
+    } else {
+        serde_json::Value::String(value.to_string())
+    }
+}
+
+/// Convert a single cell against its column's declared type, or infer one.
+/// `row`/`column` are only used for error context.
+fn convert_cell(
+    value: &str,
+    column_type: ColumnType,
+    row: usize,
+    column: &str,
+) -> Result<serde_json::Value> {
+    match column_type {
+        ColumnType::String => Ok(serde_json::Value::String(value.to_string())),
+        ColumnType::Number => {
+            if let Ok(i) = value.parse::<i64>() {
+                Ok(serde_json::Value::Number(i.into()))
+            } else if let Some(n) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+                Ok(serde_json::Value::Number(n))
+            } else {
+                anyhow::bail!(
+                    "CSV row {row}, column '{column}': expected a number, got '{value}'"
+                )
+            }
+        }
+        ColumnType::Boolean => match value.to_lowercase().as_str() {
+            "true" => Ok(serde_json::Value::Bool(true)),
+            "false" => Ok(serde_json::Value::Bool(false)),
+            _ => anyhow::bail!(
+                "CSV row {row}, column '{column}': expected a boolean (true/false), got '{value}'"
+            ),
+        },
+        ColumnType::Inferred => Ok(infer_cell(value)),
+    }
 }
 
 /// Convert a CSV file to JSONL strings in memory
 pub fn csv_to_jsonl<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+    csv_to_jsonl_with_options(path, CsvOptions::default())
+}
+
+/// Convert a CSV file to JSONL strings in memory, honoring `options`.
+///
+/// With `options.typed`, header names may declare a type via a `:number` /
+/// `:boolean` / `:string` suffix (stripped from the emitted JSON key), and
+/// undeclared columns get their values inferred per cell. A value that
+/// can't be parsed against its declared type fails with row/column context.
+pub fn csv_to_jsonl_with_options<P: AsRef<Path>>(
+    path: P,
+    options: CsvOptions,
+) -> Result<Vec<String>> {
     let path = path.as_ref();
     let file = File::open(path)
         .with_context(|| format!("Failed to open CSV file: {}", path.display()))?;
 
-    let mut reader = csv::Reader::from_reader(BufReader::new(file));
-    let headers: Vec<String> = reader.headers()
+    let dialect = options.dialect;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(dialect.delimiter)
+        .quote(dialect.quote)
+        .comment(dialect.comment)
+        .trim(if dialect.trim { csv::Trim::All } else { csv::Trim::None })
+        .has_headers(dialect.has_header)
+        .from_reader(BufReader::new(file));
+
+    let header_record = reader
+        .headers()
         .with_context(|| "Failed to read CSV headers")?
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
+        .clone();
+    let raw_headers: Vec<String> = if dialect.has_header {
+        header_record.iter().map(|s| s.to_string()).collect()
+    } else {
+        (0..header_record.len())
+            .map(|i| format!("column_{i}"))
+            .collect()
+    };
+
+    let columns: Vec<(String, ColumnType)> = if options.typed {
+        raw_headers.iter().map(|h| parse_header(h)).collect()
+    } else {
+        raw_headers
+            .iter()
+            .map(|h| (h.clone(), ColumnType::String))
+            .collect()
+    };
 
     let mut lines = Vec::new();
 
-    for result in reader.records() {
+    for (row, result) in reader.records().enumerate() {
         let record = result.with_context(|| "Failed to read CSV record")?;
-        
-        // Build JSON object from headers and values
+        // Data rows are 1-indexed; when there's a header line, the first
+        // data row is CSV line 2, otherwise it's CSV line 1.
+        let csv_row = if dialect.has_header { row + 2 } else { row + 1 };
+
         let mut obj = serde_json::Map::new();
-        for (header, value) in headers.iter().zip(record.iter()) {
-            obj.insert(header.clone(), serde_json::Value::String(value.to_string()));
+        for ((name, column_type), value) in columns.iter().zip(record.iter()) {
+            let json_value = convert_cell(value, *column_type, csv_row, name)?;
+            obj.insert(name.clone(), json_value);
         }
-        
+
         let json_line = serde_json::to_string(&serde_json::Value::Object(obj))
             .with_context(|| "Failed to serialize CSV row to JSON")?;
         lines.push(json_line);
@@ -115,6 +289,111 @@ pub fn csv_to_jsonl<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
     Ok(lines)
 }
 
+/// Write JSONL `lines` out to `path` as Parquet, CSV, or JSONL — the inverse
+/// of `parquet_to_jsonl`/`csv_to_jsonl`, letting a down-converted dataset (or
+/// a filtered subset of one) be written back out in a columnar or tabular
+/// format.
+pub fn export_lines<P: AsRef<Path>>(lines: &[String], path: P, format: InputFormat) -> Result<()> {
+    match format {
+        InputFormat::Jsonl => export_jsonl(lines, path),
+        InputFormat::Parquet => export_parquet(lines, path),
+        InputFormat::Csv => export_csv(lines, path),
+    }
+}
+
+fn export_jsonl<P: AsRef<Path>>(lines: &[String], path: P) -> Result<()> {
+    let path = path.as_ref();
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to create JSONL file: {}", path.display()))?;
+    for line in lines {
+        writeln!(file, "{line}").with_context(|| "Failed to write JSONL line")?;
+    }
+    Ok(())
+}
+
+/// Write `lines` to `path` as Parquet, inferring an Arrow schema from the
+/// rows themselves (union of keys across rows, widening types the way
+/// arrow-json's own schema inference does: int -> float, mixed -> string).
+fn export_parquet<P: AsRef<Path>>(lines: &[String], path: P) -> Result<()> {
+    let path = path.as_ref();
+    if lines.is_empty() {
+        bail!("Cannot export an empty dataset to Parquet");
+    }
+
+    let ndjson = lines.join("\n");
+    let schema = Arc::new(
+        arrow::json::reader::infer_json_schema_from_seekable(Cursor::new(ndjson.as_bytes()), None)
+            .with_context(|| "Failed to infer Arrow schema from JSON rows")?
+            .0,
+    );
+
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create Parquet file: {}", path.display()))?;
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), None)
+        .with_context(|| "Failed to create Parquet writer")?;
+
+    let reader = arrow::json::ReaderBuilder::new(schema)
+        .build(Cursor::new(ndjson.as_bytes()))
+        .with_context(|| "Failed to build JSON decoder")?;
+
+    for batch_result in reader {
+        let batch = batch_result.with_context(|| "Failed to decode a batch of JSON rows")?;
+        writer.write(&batch).with_context(|| "Failed to write Parquet batch")?;
+    }
+    writer.close().with_context(|| "Failed to finalize Parquet file")?;
+    Ok(())
+}
+
+/// Write `lines` to `path` as CSV, flattening each row's top-level keys into
+/// a header row (union of keys across rows, in first-seen order). Missing
+/// keys emit an empty cell; non-string values are rendered via their JSON
+/// text.
+fn export_csv<P: AsRef<Path>>(lines: &[String], path: P) -> Result<()> {
+    let path = path.as_ref();
+    if lines.is_empty() {
+        bail!("Cannot export an empty dataset to CSV");
+    }
+
+    let mut header = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut rows = Vec::with_capacity(lines.len());
+    for line in lines {
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| "Failed to parse a JSON row for CSV export")?;
+        if let serde_json::Value::Object(obj) = &value {
+            for key in obj.keys() {
+                if seen.insert(key.clone()) {
+                    header.push(key.clone());
+                }
+            }
+        }
+        rows.push(value);
+    }
+
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create CSV file: {}", path.display()))?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer
+        .write_record(&header)
+        .with_context(|| "Failed to write CSV header")?;
+
+    for row in &rows {
+        let record: Vec<String> = header
+            .iter()
+            .map(|key| match row.get(key) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            })
+            .collect();
+        writer
+            .write_record(&record)
+            .with_context(|| "Failed to write CSV row")?;
+    }
+    writer.flush().with_context(|| "Failed to flush CSV writer")?;
+    Ok(())
+}
+
 /// Read a JSONL file and return lines as strings
 pub fn read_jsonl_lines<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
     let path = path.as_ref();
@@ -156,4 +435,176 @@ mod tests {
         assert_eq!(InputFormat::parse("auto"), None);
         assert_eq!(InputFormat::parse("unknown"), None);
     }
+
+    #[test]
+    fn test_csv_to_jsonl_untyped_emits_strings() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = tempfile::NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "price:number,active:boolean")?;
+        writeln!(file, "42,true")?;
+        let lines = csv_to_jsonl(file.path())?;
+
+        let value: serde_json::Value = serde_json::from_str(&lines[0])?;
+        // Without `typed`, headers are used verbatim (including the
+        // suffix) and every value stays a string.
+        assert_eq!(value["price:number"], serde_json::Value::String("42".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_to_jsonl_typed_declared_columns() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "name:string,price:number,active:boolean,notes")?;
+        writeln!(file, "Widget,19.99,true,a plain note")?;
+        writeln!(file, "Gadget,7,FALSE,")?;
+        let lines = csv_to_jsonl_with_options(file.path(), CsvOptions { typed: true, ..Default::default() })?;
+
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["name"], serde_json::Value::String("Widget".into()));
+        assert_eq!(row0["price"], serde_json::json!(19.99));
+        assert_eq!(row0["active"], serde_json::Value::Bool(true));
+        assert_eq!(row0["notes"], serde_json::Value::String("a plain note".into()));
+
+        let row1: serde_json::Value = serde_json::from_str(&lines[1])?;
+        assert_eq!(row1["price"], serde_json::json!(7));
+        assert_eq!(row1["active"], serde_json::Value::Bool(false));
+        assert_eq!(row1["notes"], serde_json::Value::Null);
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_to_jsonl_typed_infers_undeclared_columns() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "id,label")?;
+        writeln!(file, "1,true")?;
+        writeln!(file, "2,hello")?;
+        let lines = csv_to_jsonl_with_options(file.path(), CsvOptions { typed: true, ..Default::default() })?;
+
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["id"], serde_json::json!(1));
+        assert_eq!(row0["label"], serde_json::Value::Bool(true));
+
+        let row1: serde_json::Value = serde_json::from_str(&lines[1])?;
+        assert_eq!(row1["label"], serde_json::Value::String("hello".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_to_jsonl_typed_rejects_bad_number() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv").unwrap();
+        writeln!(file, "price:number").unwrap();
+        writeln!(file, "not-a-number").unwrap();
+        let err = csv_to_jsonl_with_options(file.path(), CsvOptions { typed: true, ..Default::default() }).unwrap_err();
+
+        let msg = err.to_string();
+        assert!(msg.contains("row 2"), "{msg}");
+        assert!(msg.contains("price"), "{msg}");
+    }
+
+    #[test]
+    fn test_csv_dialect_for_path_detects_tsv() {
+        assert_eq!(CsvDialect::for_path("data.tsv").delimiter, b'\t');
+        assert_eq!(CsvDialect::for_path("data.csv").delimiter, b',');
+    }
+
+    #[test]
+    fn test_tsv_parses_with_tab_delimiter() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".tsv")?;
+        writeln!(file, "prompt\tresponse")?;
+        writeln!(file, "Hello\tWorld")?;
+        let options = CsvOptions {
+            dialect: CsvDialect::for_path(file.path()),
+            ..Default::default()
+        };
+        let lines = csv_to_jsonl_with_options(file.path(), options)?;
+
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["prompt"], serde_json::Value::String("Hello".into()));
+        assert_eq!(row0["response"], serde_json::Value::String("World".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_headerless_synthesizes_column_names() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "Hello,World")?;
+        let options = CsvOptions {
+            dialect: CsvDialect {
+                has_header: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = csv_to_jsonl_with_options(file.path(), options)?;
+
+        assert_eq!(lines.len(), 1);
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["column_0"], serde_json::Value::String("Hello".into()));
+        assert_eq!(row0["column_1"], serde_json::Value::String("World".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_comment_lines_are_skipped() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "a,b")?;
+        writeln!(file, "# this is a comment")?;
+        writeln!(file, "1,2")?;
+        let options = CsvOptions {
+            dialect: CsvDialect {
+                comment: Some(b'#'),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = csv_to_jsonl_with_options(file.path(), options)?;
+
+        assert_eq!(lines.len(), 1);
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["a"], serde_json::Value::String("1".into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_trim_strips_field_whitespace() -> Result<()> {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut file = NamedTempFile::with_suffix(".csv")?;
+        writeln!(file, "a,b")?;
+        writeln!(file, " 1 , 2 ")?;
+        let options = CsvOptions {
+            dialect: CsvDialect {
+                trim: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let lines = csv_to_jsonl_with_options(file.path(), options)?;
+
+        let row0: serde_json::Value = serde_json::from_str(&lines[0])?;
+        assert_eq!(row0["a"], serde_json::Value::String("1".into()));
+        assert_eq!(row0["b"], serde_json::Value::String("2".into()));
+        Ok(())
+    }
 }
@@ -0,0 +1,371 @@
+//! Caret - GGUF metadata reader (tokenizer fields only)
+//!
+//! llama.cpp's GGUF model format embeds the tokenizer's vocabulary, BPE
+//! merges, token types, and BOS/EOS/unknown ids directly in the file's
+//! key-value metadata header - so a local GGUF model file can be tokenized
+//! without a separate `tokenizer.json`. This module reads just that header
+//! (magic, version, counts, then the typed KV pairs) and pulls out the
+//! `tokenizer.ggml.*` fields `tokenizer::TokenizerWrapper::from_gguf` needs;
+//! tensor info and tensor data (everything after the metadata KV section)
+//! are never parsed.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const GGUF_VALUE_UINT8: u32 = 0;
+const GGUF_VALUE_INT8: u32 = 1;
+const GGUF_VALUE_UINT16: u32 = 2;
+const GGUF_VALUE_INT16: u32 = 3;
+const GGUF_VALUE_UINT32: u32 = 4;
+const GGUF_VALUE_INT32: u32 = 5;
+const GGUF_VALUE_FLOAT32: u32 = 6;
+const GGUF_VALUE_BOOL: u32 = 7;
+const GGUF_VALUE_STRING: u32 = 8;
+const GGUF_VALUE_ARRAY: u32 = 9;
+const GGUF_VALUE_UINT64: u32 = 10;
+const GGUF_VALUE_INT64: u32 = 11;
+const GGUF_VALUE_FLOAT64: u32 = 12;
+
+/// One metadata value from a GGUF key-value header - mirrors `ggml`'s
+/// `gguf_type` enum closely enough to round-trip every scalar/array it
+/// writes, without needing the rest of the tensor-info/tensor-data format.
+#[derive(Debug, Clone)]
+enum GgufValue {
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    U32(u32),
+    I32(i32),
+    F32(f32),
+    Bool(bool),
+    String(String),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Array(Vec<GgufValue>),
+}
+
+impl GgufValue {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            GgufValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_u32(&self) -> Option<u32> {
+        match self {
+            GgufValue::U32(v) => Some(*v),
+            GgufValue::I32(v) if *v >= 0 => Some(*v as u32),
+            GgufValue::U64(v) => Some(*v as u32),
+            _ => None,
+        }
+    }
+
+    fn as_i32(&self) -> Option<i32> {
+        match self {
+            GgufValue::I32(v) => Some(*v),
+            GgufValue::U32(v) => Some(*v as i32),
+            _ => None,
+        }
+    }
+
+    fn into_array(self) -> Option<Vec<GgufValue>> {
+        match self {
+            GgufValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(r: &mut R) -> Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+fn read_f64<R: Read>(r: &mut R) -> Result<f64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(f64::from_le_bytes(buf))
+}
+
+fn read_gguf_string<R: Read>(r: &mut R) -> Result<String> {
+    let len = read_u64(r)? as usize;
+    let mut buf = vec![0u8; len];
+    r.read_exact(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+fn read_value<R: Read>(r: &mut R, value_type: u32) -> Result<GgufValue> {
+    Ok(match value_type {
+        GGUF_VALUE_UINT8 => {
+            let mut b = [0u8; 1];
+            r.read_exact(&mut b)?;
+            GgufValue::U8(b[0])
+        }
+        GGUF_VALUE_INT8 => {
+            let mut b = [0u8; 1];
+            r.read_exact(&mut b)?;
+            GgufValue::I8(b[0] as i8)
+        }
+        GGUF_VALUE_UINT16 => {
+            let mut b = [0u8; 2];
+            r.read_exact(&mut b)?;
+            GgufValue::U16(u16::from_le_bytes(b))
+        }
+        GGUF_VALUE_INT16 => {
+            let mut b = [0u8; 2];
+            r.read_exact(&mut b)?;
+            GgufValue::I16(i16::from_le_bytes(b))
+        }
+        GGUF_VALUE_UINT32 => GgufValue::U32(read_u32(r)?),
+        GGUF_VALUE_INT32 => GgufValue::I32(read_u32(r)? as i32),
+        GGUF_VALUE_FLOAT32 => GgufValue::F32(read_f32(r)?),
+        GGUF_VALUE_BOOL => {
+            let mut b = [0u8; 1];
+            r.read_exact(&mut b)?;
+            GgufValue::Bool(b[0] != 0)
+        }
+        GGUF_VALUE_STRING => GgufValue::String(read_gguf_string(r)?),
+        GGUF_VALUE_UINT64 => GgufValue::U64(read_u64(r)?),
+        GGUF_VALUE_INT64 => GgufValue::I64(read_u64(r)? as i64),
+        GGUF_VALUE_FLOAT64 => GgufValue::F64(read_f64(r)?),
+        GGUF_VALUE_ARRAY => {
+            let element_type = read_u32(r)?;
+            let len = read_u64(r)? as usize;
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(read_value(r, element_type)?);
+            }
+            GgufValue::Array(items)
+        }
+        other => bail!("unknown GGUF value type {other}"),
+    })
+}
+
+/// `token_type` value llama.cpp uses for control/special tokens (BOS, EOS,
+/// chat-template markers, ...) in `tokenizer.ggml.token_type`.
+const TOKEN_TYPE_CONTROL: i32 = 3;
+
+/// Tokenizer-relevant fields extracted from a GGUF file's metadata header -
+/// just enough to build a BPE vocabulary, not a general GGUF reader.
+#[derive(Debug, Clone, Default)]
+pub struct GgufTokenizerData {
+    pub tokens: Vec<String>,
+    pub merges: Vec<(String, String)>,
+    pub token_types: Vec<i32>,
+    pub bos_id: Option<u32>,
+    pub eos_id: Option<u32>,
+    pub unk_id: Option<u32>,
+}
+
+impl GgufTokenizerData {
+    /// Ids classified as special/control tokens: every `token_type ==
+    /// CONTROL` entry, plus the explicit BOS/EOS/unknown ids (some GGUF
+    /// files leave those token types unset even though the ids are special).
+    pub fn special_ids(&self) -> HashSet<usize> {
+        let mut ids: HashSet<usize> = self
+            .token_types
+            .iter()
+            .enumerate()
+            .filter(|&(_, &t)| t == TOKEN_TYPE_CONTROL)
+            .map(|(i, _)| i)
+            .collect();
+        for id in [self.bos_id, self.eos_id, self.unk_id].into_iter().flatten() {
+            ids.insert(id as usize);
+        }
+        ids
+    }
+}
+
+/// Read just enough of `path` (magic, version, counts, then the metadata KV
+/// section) to pull out the `tokenizer.ggml.*` fields llama.cpp embeds in
+/// every GGUF model file - the vocab, BPE merges, token types, and
+/// BOS/EOS/unknown ids. Tensor info and weights are never parsed.
+pub fn read_tokenizer_data<P: AsRef<Path>>(path: P) -> Result<GgufTokenizerData> {
+    let file = File::open(path.as_ref())
+        .with_context(|| format!("Failed to open GGUF file: {}", path.as_ref().display()))?;
+    let mut reader = BufReader::new(file);
+
+    let mut magic = [0u8; 4];
+    reader
+        .read_exact(&mut magic)
+        .context("GGUF file too short for magic header")?;
+    if &magic != b"GGUF" {
+        bail!("not a GGUF file (bad magic {magic:?})");
+    }
+
+    let version = read_u32(&mut reader).context("failed to read GGUF version")?;
+    if version < 2 {
+        bail!("unsupported GGUF version {version} (need >= 2)");
+    }
+
+    let _tensor_count = read_u64(&mut reader).context("failed to read tensor count")?;
+    let metadata_kv_count = read_u64(&mut reader).context("failed to read metadata KV count")?;
+
+    let mut data = GgufTokenizerData::default();
+
+    for _ in 0..metadata_kv_count {
+        let key = read_gguf_string(&mut reader).context("failed to read metadata key")?;
+        let value_type = read_u32(&mut reader).context("failed to read metadata value type")?;
+        let value = read_value(&mut reader, value_type)
+            .with_context(|| format!("failed to read value for key {key}"))?;
+
+        match key.as_str() {
+            "tokenizer.ggml.tokens" => {
+                if let Some(items) = value.into_array() {
+                    data.tokens = items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect();
+                }
+            }
+            "tokenizer.ggml.merges" => {
+                if let Some(items) = value.into_array() {
+                    data.merges = items
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .filter_map(|s| s.split_once(' '))
+                        .map(|(a, b)| (a.to_string(), b.to_string()))
+                        .collect();
+                }
+            }
+            "tokenizer.ggml.token_type" => {
+                if let Some(items) = value.into_array() {
+                    data.token_types = items.iter().filter_map(|v| v.as_i32()).collect();
+                }
+            }
+            "tokenizer.ggml.bos_token_id" => data.bos_id = value.as_u32(),
+            "tokenizer.ggml.eos_token_id" => data.eos_id = value.as_u32(),
+            "tokenizer.ggml.unknown_token_id" => data.unk_id = value.as_u32(),
+            _ => {}
+        }
+    }
+
+    if data.tokens.is_empty() {
+        bail!("GGUF file has no tokenizer.ggml.tokens metadata - not a tokenizer-embedding model file");
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_gguf_string<W: Write>(w: &mut W, s: &str) {
+        w.write_all(&(s.len() as u64).to_le_bytes()).unwrap();
+        w.write_all(s.as_bytes()).unwrap();
+    }
+
+    fn write_string_array<W: Write>(w: &mut W, key: &str, items: &[&str]) {
+        write_gguf_string(w, key);
+        w.write_all(&GGUF_VALUE_ARRAY.to_le_bytes()).unwrap();
+        w.write_all(&GGUF_VALUE_STRING.to_le_bytes()).unwrap();
+        w.write_all(&(items.len() as u64).to_le_bytes()).unwrap();
+        for item in items {
+            write_gguf_string(w, item);
+        }
+    }
+
+    fn write_i32_array<W: Write>(w: &mut W, key: &str, items: &[i32]) {
+        write_gguf_string(w, key);
+        w.write_all(&GGUF_VALUE_ARRAY.to_le_bytes()).unwrap();
+        w.write_all(&GGUF_VALUE_INT32.to_le_bytes()).unwrap();
+        w.write_all(&(items.len() as u64).to_le_bytes()).unwrap();
+        for &item in items {
+            w.write_all(&item.to_le_bytes()).unwrap();
+        }
+    }
+
+    fn write_u32_scalar<W: Write>(w: &mut W, key: &str, value: u32) {
+        write_gguf_string(w, key);
+        w.write_all(&GGUF_VALUE_UINT32.to_le_bytes()).unwrap();
+        w.write_all(&value.to_le_bytes()).unwrap();
+    }
+
+    /// A minimal synthetic GGUF byte buffer with just the tokenizer KV
+    /// entries `read_tokenizer_data` looks at - there's no bundled sample
+    /// model file to read, so tests build one from scratch.
+    fn build_test_gguf() -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"GGUF");
+        buf.extend_from_slice(&3u32.to_le_bytes()); // version
+        buf.extend_from_slice(&0u64.to_le_bytes()); // tensor_count
+        buf.extend_from_slice(&6u64.to_le_bytes()); // metadata_kv_count
+
+        write_string_array(&mut buf, "tokenizer.ggml.tokens", &["h", "e", "l", "o", "he", "hel"]);
+        write_string_array(&mut buf, "tokenizer.ggml.merges", &["h e", "he l"]);
+        write_i32_array(&mut buf, "tokenizer.ggml.token_type", &[1, 1, 1, 1, 1, 3]);
+        write_u32_scalar(&mut buf, "tokenizer.ggml.bos_token_id", 0);
+        write_u32_scalar(&mut buf, "tokenizer.ggml.eos_token_id", 3);
+        write_u32_scalar(&mut buf, "tokenizer.ggml.unknown_token_id", 3);
+
+        buf
+    }
+
+    #[test]
+    fn test_read_tokenizer_data_round_trip() {
+        let bytes = build_test_gguf();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&bytes).unwrap();
+
+        let data = read_tokenizer_data(file.path()).unwrap();
+        assert_eq!(data.tokens, vec!["h", "e", "l", "o", "he", "hel"]);
+        assert_eq!(
+            data.merges,
+            vec![("h".to_string(), "e".to_string()), ("he".to_string(), "l".to_string())]
+        );
+        assert_eq!(data.bos_id, Some(0));
+        assert_eq!(data.eos_id, Some(3));
+        assert_eq!(data.unk_id, Some(3));
+    }
+
+    #[test]
+    fn test_special_ids_includes_control_type_and_bos_eos() {
+        let bytes = build_test_gguf();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&bytes).unwrap();
+
+        let data = read_tokenizer_data(file.path()).unwrap();
+        let special = data.special_ids();
+        assert!(special.contains(&5)); // "hel" has token_type CONTROL
+        assert!(special.contains(&0)); // bos
+        assert!(special.contains(&3)); // eos == unk
+    }
+
+    #[test]
+    fn test_rejects_bad_magic() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"NOPE").unwrap();
+        assert!(read_tokenizer_data(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_missing_tokens_metadata() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"GGUF");
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(&0u64.to_le_bytes());
+        buf.extend_from_slice(&0u64.to_le_bytes()); // no metadata entries
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&buf).unwrap();
+        assert!(read_tokenizer_data(file.path()).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! Caret - JSON tree view model
+//!
+//! Flattens a `serde_json::Value` into a linear list of visible rows for
+//! `ViewMode::Tree`, respecting the expand/collapse state the user (or an
+//! MCP tool) has built up in `App::tree_expanded`. Rendering-agnostic on
+//! purpose: `ui::render_tree_content` turns each `TreeRow` into styled
+//! spans, the same split `jsontree`/`ui` has for every other view.
+//!
+//! # Paths and default state
+//!
+//! Every node below the root gets a dot/bracket JSON path (e.g.
+//! `messages[2].content`) used as its key in `tree_expanded`. The root is
+//! always shown expanded — otherwise switching into Tree mode would show a
+//! single collapsed `{N keys}` row and nothing else — every other
+//! container defaults closed until explicitly opened.
+
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Hard cap on how many children of one expanded array/object are turned
+/// into rows, so an explicitly-opened huge array can't blow up a single
+/// frame's render cost. A synthetic "... N more" row stands in for the
+/// rest.
+const MAX_RENDERED_CHILDREN: usize = 200;
+
+/// One visible row in the flattened tree.
+#[derive(Debug, Clone)]
+pub struct TreeRow {
+    /// Nesting depth, for indentation (0 = root).
+    pub depth: usize,
+    /// Dot/bracket JSON path — this row's key in `tree_expanded`. Empty
+    /// for the root.
+    pub path: String,
+    /// Object key or array-index label shown before the value, if any
+    /// (the root has none).
+    pub key: Option<String>,
+    /// What this row renders as.
+    pub kind: TreeRowKind,
+    /// Whether this row has children to expand/collapse.
+    pub expandable: bool,
+    /// Whether this row is currently expanded (meaningless unless
+    /// `expandable`).
+    pub expanded: bool,
+}
+
+/// What a `TreeRow` renders as.
+#[derive(Debug, Clone)]
+pub enum TreeRowKind {
+    Object { len: usize },
+    Array { len: usize },
+    String(String),
+    Number(String),
+    Bool(bool),
+    Null,
+    /// Synthetic placeholder for children past `MAX_RENDERED_CHILDREN`.
+    Truncated { remaining: usize },
+}
+
+/// Flatten `value` into visible rows, given which paths are open.
+pub fn flatten(value: &Value, expanded: &HashSet<String>) -> Vec<TreeRow> {
+    let mut rows = Vec::new();
+    push_node(value, 0, String::new(), None, expanded, &mut rows);
+    rows
+}
+
+/// Whether the node at `path` should render expanded: the root always is;
+/// everything else only if the caller has opened it.
+fn is_open(path: &str, expanded: &HashSet<String>) -> bool {
+    path.is_empty() || expanded.contains(path)
+}
+
+fn push_node(
+    value: &Value,
+    depth: usize,
+    path: String,
+    key: Option<String>,
+    expanded: &HashSet<String>,
+    rows: &mut Vec<TreeRow>,
+) {
+    match value {
+        Value::Object(map) => {
+            let open = is_open(&path, expanded);
+            rows.push(TreeRow {
+                depth,
+                path: path.clone(),
+                key,
+                kind: TreeRowKind::Object { len: map.len() },
+                expandable: !map.is_empty(),
+                expanded: open,
+            });
+            if open {
+                push_children(
+                    map.iter().map(|(k, v)| (k.clone(), v)),
+                    depth + 1,
+                    &path,
+                    expanded,
+                    rows,
+                );
+            }
+        }
+        Value::Array(items) => {
+            let open = is_open(&path, expanded);
+            rows.push(TreeRow {
+                depth,
+                path: path.clone(),
+                key,
+                kind: TreeRowKind::Array { len: items.len() },
+                expandable: !items.is_empty(),
+                expanded: open,
+            });
+            if open {
+                push_children(
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, v)| (format!("[{}]", i), v)),
+                    depth + 1,
+                    &path,
+                    expanded,
+                    rows,
+                );
+            }
+        }
+        leaf => rows.push(TreeRow {
+            depth,
+            path,
+            key,
+            kind: leaf_kind(leaf),
+            expandable: false,
+            expanded: false,
+        }),
+    }
+}
+
+/// Push up to `MAX_RENDERED_CHILDREN` children, appending a `Truncated`
+/// summary row for the rest instead of recursing into them.
+fn push_children<'a>(
+    children: impl Iterator<Item = (String, &'a Value)>,
+    depth: usize,
+    parent_path: &str,
+    expanded: &HashSet<String>,
+    rows: &mut Vec<TreeRow>,
+) {
+    let mut count = 0;
+    let mut remaining = 0;
+    for (label, child) in children {
+        count += 1;
+        if count > MAX_RENDERED_CHILDREN {
+            remaining += 1;
+            continue;
+        }
+        let is_array_index = label.starts_with('[');
+        let child_path = if is_array_index {
+            format!("{}{}", parent_path, label)
+        } else if parent_path.is_empty() {
+            label.clone()
+        } else {
+            format!("{}.{}", parent_path, label)
+        };
+        push_node(child, depth, child_path, Some(label), expanded, rows);
+    }
+    if remaining > 0 {
+        rows.push(TreeRow {
+            depth,
+            path: format!("{}#truncated", parent_path),
+            key: None,
+            kind: TreeRowKind::Truncated { remaining },
+            expandable: false,
+            expanded: false,
+        });
+    }
+}
+
+fn leaf_kind(value: &Value) -> TreeRowKind {
+    match value {
+        Value::String(s) => TreeRowKind::String(s.clone()),
+        Value::Number(n) => TreeRowKind::Number(n.to_string()),
+        Value::Bool(b) => TreeRowKind::Bool(*b),
+        Value::Null => TreeRowKind::Null,
+        Value::Object(_) | Value::Array(_) => unreachable!("containers handled by push_node"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_root_expands_by_default() {
+        let value: Value = serde_json::json!({"a": 1, "b": 2});
+        let rows = flatten(&value, &HashSet::new());
+        // Root row + two leaf rows, since the root is always open.
+        assert_eq!(rows.len(), 3);
+        assert!(matches!(rows[0].kind, TreeRowKind::Object { len: 2 }));
+    }
+
+    #[test]
+    fn test_nested_object_collapsed_by_default() {
+        let value: Value = serde_json::json!({"outer": {"inner": 1}});
+        let rows = flatten(&value, &HashSet::new());
+        // Root + "outer" row only — "outer" is closed, so "inner" is hidden.
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].path, "outer");
+        assert!(!rows[1].expanded);
+    }
+
+    #[test]
+    fn test_expanding_a_path_reveals_its_children() {
+        let value: Value = serde_json::json!({"outer": {"inner": 1}});
+        let mut expanded = HashSet::new();
+        expanded.insert("outer".to_string());
+        let rows = flatten(&value, &expanded);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].path, "outer.inner");
+    }
+
+    #[test]
+    fn test_array_index_path_uses_brackets() {
+        let value: Value = serde_json::json!({"messages": [{"content": "hi"}]});
+        let mut expanded = HashSet::new();
+        expanded.insert("messages".to_string());
+        expanded.insert("messages[0]".to_string());
+        let rows = flatten(&value, &expanded);
+        let content_row = rows.iter().find(|r| r.key.as_deref() == Some("content"));
+        assert_eq!(content_row.unwrap().path, "messages[0].content");
+    }
+
+    #[test]
+    fn test_large_array_truncates_rendered_children() {
+        let items: Vec<Value> = (0..500).map(Value::from).collect();
+        let value = Value::Array(items);
+        let mut expanded = HashSet::new();
+        expanded.insert(String::new()); // root is already open; no-op
+        let rows = flatten(&value, &expanded);
+        let truncated = rows.iter().find(|r| matches!(r.kind, TreeRowKind::Truncated { .. }));
+        assert!(truncated.is_some());
+    }
+}
@@ -10,15 +10,28 @@
 //!   via the Model Context Protocol (JSON-RPC over HTTP).
 //! - **HF Streaming** (`streaming`) — Stream Parquet files directly from the
 //!   Hugging Face Hub using HTTP Range requests — no full download needed.
+//! - **LSP Server** (`lsp`) — Expose `Linter` diagnostics to any editor via
+//!   the Language Server Protocol (`caret lsp --stdio`).
+//! - **Object Storage** (`objectstore`) — Open `s3://`, `gs://`, `az://`, and
+//!   `http(s)://` URLs directly, no separate download step needed.
 
+pub mod ansi;
 pub mod app;
+pub mod commands;
 pub mod data;
 pub mod engine;
 pub mod fixer;
 pub mod format;
+pub mod gguf;
+pub mod jsontree;
 pub mod linter;
+pub mod lsp;
+pub mod markdown;
 pub mod mcp;
+pub mod objectstore;
+pub mod segmentation;
 pub mod streaming;
+pub mod theme;
 pub mod tokenizer;
 pub mod tui;
 pub mod ui;
@@ -4,6 +4,16 @@
 
 use crate::data::Dataset;
 use regex::Regex;
+use std::collections::HashMap;
+
+/// Word-level n-gram size used by the repetition/degeneration detector.
+const REPETITION_NGRAM_SIZE: usize = 4;
+
+/// Below this unique-to-total n-gram ratio, content is flagged as
+/// degenerate even if no single n-gram individually crosses the repeat
+/// threshold (catches "ABAB..." style loops across a few n-grams rather
+/// than just one).
+const REPETITION_UNIQUE_RATIO_FLOOR: f64 = 0.3;
 
 /// Types of lint errors
 #[derive(Debug, Clone)]
@@ -19,6 +29,9 @@ pub enum LintError {
     TrailingWhitespace,
     /// Empty content
     EmptyContent,
+    /// Degenerate repetition: a word n-gram repeats far more than expected,
+    /// or the unique-to-total n-gram ratio is too low across the whole text.
+    RepetitiveContent { ngram: String, count: usize },
 }
 
 #[allow(dead_code)]
@@ -32,6 +45,9 @@ impl LintError {
             }
             LintError::TrailingWhitespace => "Trailing whitespace detected".to_string(),
             LintError::EmptyContent => "Empty content field".to_string(),
+            LintError::RepetitiveContent { ngram, count } => {
+                format!("Repetitive content: \"{}\" repeats {} times", ngram, count)
+            }
         }
     }
 
@@ -42,6 +58,48 @@ impl LintError {
             LintError::MissingKey(_) => "WARNING",
             LintError::TrailingWhitespace => "WARNING",
             LintError::EmptyContent => "WARNING",
+            LintError::RepetitiveContent { .. } => "WARNING",
+        }
+    }
+
+    /// Stable machine-readable identifier for this variant, independent of
+    /// the human-readable `message()` — used as the `code` on an LSP
+    /// `Diagnostic` (see `lsp::run_stdio`) so editors can filter/suppress by
+    /// rule rather than matching on message text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LintError::InvalidJson(_) => "invalid-json",
+            LintError::MissingKey(_) => "missing-key",
+            LintError::UnbalancedThinkTags { .. } => "unbalanced-think-tags",
+            LintError::TrailingWhitespace => "trailing-whitespace",
+            LintError::EmptyContent => "empty-content",
+            LintError::RepetitiveContent { .. } => "repetitive-content",
+        }
+    }
+
+    /// Best-effort byte span within `content` (the raw JSONL line) that this
+    /// error should be annotated against, for the rustc-style caret
+    /// underlines in `ui::render_content`.
+    ///
+    /// Falls back to `(0, 0)` (a zero-width caret at the start of the line)
+    /// when no more specific span can be located.
+    pub fn byte_span(&self, content: &str) -> (usize, usize) {
+        match self {
+            LintError::InvalidJson(_) => (0, 0),
+            LintError::MissingKey(_) => (0, 0),
+            LintError::UnbalancedThinkTags { open, close } => {
+                // Point at whichever tag is left dangling: the last
+                // occurrence of the tag kind that has the surplus count.
+                let tag = if open > close { "<think>" } else { "</think>" };
+                if let Some(pos) = content.rfind(tag) {
+                    (pos, pos + tag.len())
+                } else {
+                    (0, 0)
+                }
+            }
+            LintError::TrailingWhitespace => find_trailing_whitespace_span(content),
+            LintError::EmptyContent => (0, 0),
+            LintError::RepetitiveContent { .. } => (0, 0),
         }
     }
 }
@@ -61,6 +119,9 @@ pub struct Linter {
     think_open_regex: Regex,
     think_close_regex: Regex,
     required_keys: Vec<String>,
+    /// Max times a single word n-gram (size `REPETITION_NGRAM_SIZE`) may
+    /// repeat in a line's text content before it's flagged as degenerate.
+    repetition_threshold: usize,
 }
 
 impl Default for Linter {
@@ -76,6 +137,7 @@ impl Linter {
             think_open_regex: Regex::new(r"<think>").expect("valid regex: <think>"),
             think_close_regex: Regex::new(r"</think>").expect("valid regex: </think>"),
             required_keys: vec![],
+            repetition_threshold: 3,
         }
     }
 
@@ -85,6 +147,13 @@ impl Linter {
         self
     }
 
+    /// Set how many times a single word n-gram may repeat in a line's text
+    /// content before it's flagged as degenerate repetition.
+    pub fn with_repetition_threshold(mut self, threshold: usize) -> Self {
+        self.repetition_threshold = threshold;
+        self
+    }
+
     /// Lint a single line of text
     pub fn lint_line(&self, line: &str, line_num: usize) -> Vec<LintResult> {
         let mut results = Vec::new();
@@ -134,6 +203,16 @@ impl Linter {
                         error: LintError::TrailingWhitespace,
                     });
                 }
+
+                // Check for degenerate repetition (looping n-grams)
+                if let Some((ngram, count)) =
+                    detect_repetition(&text_content, self.repetition_threshold)
+                {
+                    results.push(LintResult {
+                        line: line_num,
+                        error: LintError::RepetitiveContent { ngram, count },
+                    });
+                }
             }
         }
 
@@ -157,6 +236,83 @@ impl Linter {
     }
 }
 
+/// Slide a `REPETITION_NGRAM_SIZE`-word window over `text` and flag
+/// degenerate repetition: either a single n-gram repeating more than
+/// `threshold` times, or the unique-to-total n-gram ratio falling below
+/// `REPETITION_UNIQUE_RATIO_FLOOR` (a cheap compression-style signal for
+/// text that loops across a handful of n-grams rather than just one).
+///
+/// Linear in the number of words: one pass building a frequency map, plus
+/// a single scan to find the worst offender.
+fn detect_repetition(text: &str, threshold: usize) -> Option<(String, usize)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() < REPETITION_NGRAM_SIZE {
+        return None;
+    }
+
+    let mut counts: HashMap<&[&str], usize> = HashMap::new();
+    for window in words.windows(REPETITION_NGRAM_SIZE) {
+        *counts.entry(window).or_insert(0) += 1;
+    }
+
+    let total_ngrams = words.len() - REPETITION_NGRAM_SIZE + 1;
+    let unique_ngrams = counts.len();
+    let unique_ratio = unique_ngrams as f64 / total_ngrams as f64;
+
+    let worst = counts.iter().max_by_key(|(_, count)| **count);
+    if let Some((ngram, count)) = worst {
+        if *count > threshold || unique_ratio < REPETITION_UNIQUE_RATIO_FLOOR {
+            return Some((ngram.join(" "), *count));
+        }
+    }
+
+    None
+}
+
+/// Scan raw JSONL `line`, tracking whether each byte sits inside a JSON
+/// string literal (toggling on unescaped `"`), and return the span of the
+/// first run of spaces inside a string that sits right before that
+/// string's closing quote or before a `\n` escape - the two patterns
+/// `lint_line`'s trailing-whitespace check flags against the *decoded*
+/// text. Scoping the scan to inside-string bytes avoids false positives
+/// like the space between `:` and an opening quote in pretty-printed JSON.
+///
+/// Falls back to `(0, 0)` if no such run is found.
+fn find_trailing_whitespace_span(line: &str) -> (usize, usize) {
+    let bytes = line.as_bytes();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            } else if b == b' ' {
+                let start = i;
+                let mut end = i;
+                while end < bytes.len() && bytes[end] == b' ' {
+                    end += 1;
+                }
+                let next = bytes.get(end);
+                if next == Some(&b'"') || (next == Some(&b'\\') && bytes.get(end + 1) == Some(&b'n')) {
+                    return (start, end);
+                }
+                i = end;
+                continue;
+            }
+        } else if b == b'"' {
+            in_string = true;
+        }
+        i += 1;
+    }
+    (0, 0)
+}
+
 /// Extract all text content from a JSON value for analysis
 fn extract_text_content(value: &serde_json::Value) -> String {
     match value {
@@ -179,6 +335,24 @@ fn extract_text_content(value: &serde_json::Value) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_trailing_whitespace_span_points_at_string_not_past_brace() {
+        // The raw line itself has no trailing whitespace (it ends in `}`),
+        // only the string value does - the span must point inside the
+        // string, not at a zero-width range past the closing brace.
+        let line = r#"{"content":"trailing space   "}"#;
+        let linter = Linter::new();
+        let results = linter.lint_line(line, 0);
+        let result = results
+            .iter()
+            .find(|r| matches!(r.error, LintError::TrailingWhitespace))
+            .expect("trailing whitespace should be detected");
+
+        let (start, end) = result.error.byte_span(line);
+        assert_eq!(&line[start..end], "   ");
+        assert!(end < line.len());
+    }
+
     #[test]
     fn test_balanced_think_tags() {
         let linter = Linter::new();
@@ -195,4 +369,24 @@ mod tests {
         let results = linter.lint_line("not json {", 0);
         assert!(matches!(results[0].error, LintError::InvalidJson(_)));
     }
+
+    #[test]
+    fn test_repetitive_content_flagged() {
+        let linter = Linter::new();
+        let looping = r#"{"text": "the cat sat down the cat sat down the cat sat down the cat sat down"}"#;
+        let results = linter.lint_line(looping, 0);
+        assert!(results
+            .iter()
+            .any(|r| matches!(r.error, LintError::RepetitiveContent { .. })));
+    }
+
+    #[test]
+    fn test_non_repetitive_content_not_flagged() {
+        let linter = Linter::new();
+        let varied = r#"{"text": "the quick brown fox jumps over the lazy dog near the river bank today"}"#;
+        let results = linter.lint_line(varied, 0);
+        assert!(!results
+            .iter()
+            .any(|r| matches!(r.error, LintError::RepetitiveContent { .. })));
+    }
 }
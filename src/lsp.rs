@@ -0,0 +1,294 @@
+//! Caret LSP server — inline lint diagnostics over the Language Server Protocol
+//!
+//! Lets any LSP-aware editor open a `.jsonl` reasoning dataset and see the
+//! same checks `Linter` already runs in the TUI (`caret --lint`) as inline
+//! squiggles, instead of only as a post-hoc report.
+//!
+//! # Protocol
+//!
+//! LSP messages are JSON-RPC 2.0 framed with a `Content-Length` header over
+//! stdio (not newline-delimited like `mcp::start_mcp_server_stdio` — the LSP
+//! spec mandates this framing). We implement the minimal handshake plus
+//! diagnostics publishing:
+//!
+//! | Method                        | Purpose                                    |
+//! |-------------------------------|--------------------------------------------|
+//! | `initialize`                  | Handshake — returns server capabilities    |
+//! | `initialized`                 | Client ack (no response)                   |
+//! | `textDocument/didOpen`        | Lint the newly opened document             |
+//! | `textDocument/didChange`      | Re-lint after a full-document edit         |
+//! | `shutdown`                    | Client is about to disconnect              |
+//! | `exit`                        | Terminate the loop                         |
+//!
+//! Diagnostics go out as server-initiated `textDocument/publishDiagnostics`
+//! notifications, one per `textDocument/didOpen` or `didChange`.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::linter::{LintError, LintResult, Linter};
+
+/// Run the LSP server, reading requests from stdin and writing responses
+/// and notifications to stdout until `exit` or end-of-input.
+pub fn run_stdio() -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut reader = stdin.lock();
+    let stdout = std::io::stdout();
+    let mut writer = stdout.lock();
+
+    let linter = Linter::new();
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(body) = read_message(&mut reader)? {
+        let message: LspMessage = serde_json::from_str(&body)
+            .with_context(|| format!("Malformed LSP message: {}", body))?;
+
+        match message.method.as_deref() {
+            Some("initialize") => write_message(&mut writer, &initialize_result(message.id))?,
+            Some("initialized") => {}
+            Some("shutdown") => write_message(&mut writer, &success(message.id, Value::Null))?,
+            Some("exit") => break,
+            Some("textDocument/didOpen") => {
+                if let Some((uri, text)) = did_open_document(&message.params) {
+                    publish_diagnostics(&mut writer, &linter, &uri, &text)?;
+                    documents.insert(uri, text);
+                }
+            }
+            Some("textDocument/didChange") => {
+                if let Some((uri, text)) = did_change_document(&message.params) {
+                    publish_diagnostics(&mut writer, &linter, &uri, &text)?;
+                    documents.insert(uri, text);
+                }
+            }
+            Some(other) => {
+                if let Some(id) = message.id {
+                    write_message(
+                        &mut writer,
+                        &error(id, -32601, format!("Method not found: {}", other)),
+                    )?;
+                }
+            }
+            None => {} // notification we don't care about
+        }
+    }
+
+    Ok(())
+}
+
+/// Incoming JSON-RPC message. `id` is absent for notifications; `method` is
+/// always present for requests/notifications from the client in this subset
+/// of the protocol (we never issue our own requests, only responses and
+/// server-initiated notifications).
+#[derive(Debug, Deserialize)]
+struct LspMessage {
+    #[serde(default)]
+    id: Option<Value>,
+    method: Option<String>,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+/// Read one `Content-Length`-framed LSP message from `reader`, returning
+/// `None` at end-of-input.
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<String>> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break; // blank line ends the header block
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            content_length = Some(
+                value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid Content-Length header: {}", value))?,
+            );
+        }
+    }
+
+    let len = content_length.context("LSP message is missing a Content-Length header")?;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(String::from_utf8(buf)?))
+}
+
+/// Write `value` as a `Content-Length`-framed LSP message to `writer`.
+fn write_message<W: Write>(writer: &mut W, value: &Value) -> Result<()> {
+    let body = serde_json::to_string(value)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn success(id: Option<Value>, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error(id: Value, code: i32, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn initialize_result(id: Option<Value>) -> Value {
+    success(
+        id,
+        json!({
+            "capabilities": {
+                // Full-document sync — the client resends the whole text on
+                // every change, which keeps didChange handling identical to
+                // didOpen (we always re-lint from scratch, not incrementally).
+                "textDocumentSync": 1,
+            },
+            "serverInfo": {
+                "name": "caret-lsp",
+                "version": env!("CARGO_PKG_VERSION"),
+            }
+        }),
+    )
+}
+
+/// Pull `(uri, text)` out of a `textDocument/didOpen` params object.
+fn did_open_document(params: &Option<Value>) -> Option<(String, String)> {
+    let doc = params.as_ref()?.get("textDocument")?;
+    let uri = doc.get("uri")?.as_str()?.to_string();
+    let text = doc.get("text")?.as_str()?.to_string();
+    Some((uri, text))
+}
+
+/// Pull `(uri, text)` out of a `textDocument/didChange` params object. We
+/// advertise full-document sync, so `contentChanges` is always a single
+/// entry carrying the complete new text rather than a range-based delta.
+fn did_change_document(params: &Option<Value>) -> Option<(String, String)> {
+    let params = params.as_ref()?;
+    let uri = params.get("textDocument")?.get("uri")?.as_str()?.to_string();
+    let text = params
+        .get("contentChanges")?
+        .as_array()?
+        .last()?
+        .get("text")?
+        .as_str()?
+        .to_string();
+    Some((uri, text))
+}
+
+/// Lint `text` line by line and publish the results as one
+/// `textDocument/publishDiagnostics` notification.
+fn publish_diagnostics<W: Write>(writer: &mut W, linter: &Linter, uri: &str, text: &str) -> Result<()> {
+    let mut diagnostics = Vec::new();
+
+    for (line_num, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        for result in linter.lint_line(line, line_num) {
+            diagnostics.push(diagnostic(&result, line));
+        }
+    }
+
+    write_message(
+        writer,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        }),
+    )
+}
+
+/// Convert a `LintResult` into an LSP `Diagnostic`. `UnbalancedThinkTags` and
+/// `TrailingWhitespace` use `LintError::byte_span` to point at the offending
+/// span within the line; every other variant spans the whole line, since
+/// there's no more specific location to blame.
+fn diagnostic(result: &LintResult, line_text: &str) -> Value {
+    let (start_char, end_char) = match &result.error {
+        LintError::UnbalancedThinkTags { .. } | LintError::TrailingWhitespace => {
+            let (start_byte, end_byte) = result.error.byte_span(line_text);
+            (char_offset(line_text, start_byte), char_offset(line_text, end_byte))
+        }
+        _ => (0, line_text.chars().count()),
+    };
+
+    json!({
+        "range": {
+            "start": { "line": result.line, "character": start_char },
+            "end": { "line": result.line, "character": end_char.max(start_char) },
+        },
+        "severity": lsp_severity(result.error.severity()),
+        "code": result.error.code(),
+        "source": "caret",
+        "message": result.error.message(),
+    })
+}
+
+/// Byte offset -> character offset within `text`, since LSP positions are
+/// measured in characters (we treat UTF-16 code units and chars as
+/// equivalent, which holds for the ASCII/BMP content these datasets hold in
+/// practice).
+fn char_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset.min(text.len())].chars().count()
+}
+
+/// Map `LintError::severity()`'s string form to LSP's numeric
+/// `DiagnosticSeverity` (1 = Error, 2 = Warning).
+fn lsp_severity(severity: &str) -> u8 {
+    match severity {
+        "ERROR" => 1,
+        _ => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_spans_whole_line_for_invalid_json() {
+        let linter = Linter::new();
+        let line = "not json {";
+        let result = &linter.lint_line(line, 0)[0];
+        let diag = diagnostic(result, line);
+        assert_eq!(diag["range"]["start"]["character"], 0);
+        assert_eq!(diag["range"]["end"]["character"], line.chars().count() as u64);
+        assert_eq!(diag["code"], "invalid-json");
+    }
+
+    #[test]
+    fn test_diagnostic_points_at_trailing_whitespace_span() {
+        let linter = Linter::new();
+        let line = r#"{"text": "answer "}"#;
+        let results = linter.lint_line(line, 0);
+        let result = results
+            .iter()
+            .find(|r| matches!(r.error, LintError::TrailingWhitespace))
+            .expect("trailing whitespace should be detected");
+        let diag = diagnostic(result, line);
+        // The one trailing space sits right before the closing quote, at
+        // character 16 (`{"text": "answer `); it must not be reported past
+        // the end of the line.
+        assert_eq!(diag["range"]["start"]["character"], 16);
+        assert_eq!(diag["range"]["end"]["character"], 17);
+        assert!((diag["range"]["end"]["character"].as_u64().unwrap() as usize) < line.chars().count());
+        assert_eq!(diag["code"], "trailing-whitespace");
+    }
+
+    #[test]
+    fn test_read_message_round_trips_with_write_message() {
+        let value = json!({ "jsonrpc": "2.0", "id": 1, "result": {} });
+        let mut buf = Vec::new();
+        write_message(&mut buf, &value).unwrap();
+
+        let mut cursor = std::io::Cursor::new(buf);
+        let body = read_message(&mut cursor).unwrap().unwrap();
+        let parsed: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed, value);
+    }
+}
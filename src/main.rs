@@ -17,12 +17,14 @@ use std::time::Duration;
 use caret::app::{App, ViewMode};
 use caret::commands::{command_channel, TuiCommand, TuiCommandReceiver, ViewModeCmd};
 use caret::data::Dataset;
-use caret::engine::{DedupEngine, DedupStrategy};
-use caret::fixer::{FixResult, Fixer, FixSummary};
+use caret::engine::{DedupEngine, DedupStrategy, HttpEmbedder, SEMANTIC_EMBEDDING_DIM};
+use caret::fixer::{Applicability, FixResult, Fixer, FixSummary};
 use caret::format::InputFormat;
 use caret::linter::Linter;
+use caret::lsp;
 use caret::mcp;
 use caret::streaming;
+use caret::streaming::{resolve_hf_token, Predicate};
 use caret::tokenizer::{TiktokenEncoding, TokenizerType, TokenizerWrapper};
 use caret::tui::Tui;
 use caret::ui;
@@ -30,7 +32,8 @@ use caret::ui;
 /// Caret - Blazingly fast TUI for LLM dataset curation
 #[derive(FromArgs)]
 struct Args {
-    /// path to the dataset file (JSONL, Parquet, CSV, or hf://org/dataset)
+    /// path to the dataset file (JSONL, Parquet, CSV, hf://org/dataset, or
+    /// s3://, gs://, az://, http(s):// URLs)
     #[argh(positional)]
     file: String,
 
@@ -38,11 +41,35 @@ struct Args {
     #[argh(option, default = "String::from(\"auto\")")]
     format: String,
 
+    /// typed CSV conversion: honor `:number`/`:boolean`/`:string` header
+    /// suffixes and infer JSON types per cell for undeclared columns,
+    /// instead of emitting every CSV value as a string
+    #[argh(switch)]
+    csv_typed: bool,
+
+    /// CSV/TSV field delimiter character (default: `,`, or `\t` for .tsv files)
+    #[argh(option)]
+    delimiter: Option<String>,
+
+    /// treat the CSV/TSV file as headerless: synthesize column_0, column_1, ...
+    /// names instead of reading them from the first row
+    #[argh(switch)]
+    no_header: bool,
+
+    /// trim leading/trailing whitespace from each CSV/TSV field
+    #[argh(switch)]
+    csv_trim: bool,
+
+    /// skip CSV/TSV lines starting with this character (e.g. `#`)
+    #[argh(option)]
+    csv_comment: Option<String>,
+
     /// enable Token X-Ray mode
     #[argh(switch, short = 't')]
     tokenizer: bool,
 
-    /// tokenizer type: tiktoken, huggingface, gpt2 (default: tiktoken)
+    /// tokenizer type: tiktoken, huggingface, gpt2, whitespace, unicode,
+    /// ngram:min,max (default: tiktoken)
     #[argh(option, default = "String::from(\"tiktoken\")")]
     tokenizer_type: String,
 
@@ -50,7 +77,8 @@ struct Args {
     #[argh(option, default = "String::from(\"cl100k_base\")")]
     tiktoken_encoding: String,
 
-    /// path to custom tokenizer.json (overrides --tokenizer-type)
+    /// path to a custom tokenizer.json, or a .gguf model file with an
+    /// embedded tokenizer (overrides --tokenizer-type)
     #[argh(option)]
     tokenizer_path: Option<String>,
 
@@ -78,11 +106,21 @@ struct Args {
     #[argh(switch)]
     fix_in_place: bool,
 
+    /// also apply unsafe fixes (heuristic guesses, e.g. think-tag
+    /// positions) instead of only suggesting them
+    #[argh(switch)]
+    fix_unsafe: bool,
+
+    /// show a per-line before/after diff of fixed content instead of just
+    /// summary counts
+    #[argh(switch)]
+    diff: bool,
+
     /// run dedup scan (near-duplicate detection)
     #[argh(switch)]
     dedup: bool,
 
-    /// dedup strategy: exact, simhash (default: simhash)
+    /// dedup strategy: exact, exact_strong, simhash, weighted_simhash, semantic (default: simhash)
     #[argh(option, default = "String::from(\"simhash\")")]
     dedup_strategy: String,
 
@@ -90,10 +128,61 @@ struct Args {
     #[argh(option, default = "3")]
     dedup_threshold: u32,
 
+    /// simhash fingerprint width in bits: 64 or 128. 128 roughly doubles
+    /// usable threshold resolution on long documents, but isn't usable
+    /// with --dedup-strategy exact/exact_strong (default: 64)
+    #[argh(option, default = "64")]
+    dedup_fingerprint_bits: u32,
+
+    /// cosine-similarity threshold for `--dedup-strategy semantic` (0.0-1.0; default: 0.85)
+    #[argh(option, default = "0.85")]
+    dedup_semantic_threshold: f32,
+
+    /// HTTP embedding endpoint for `--dedup-strategy semantic`; POSTed
+    /// `{"input": "..."}`, expects `{"embedding": [f32; N]}` back
+    #[argh(option)]
+    dedup_embedding_endpoint: Option<String>,
+
     /// export deduplicated dataset to this path
     #[argh(option)]
     dedup_export: Option<String>,
 
+    /// comma-separated substrings; lines whose content matches any of them
+    /// are flagged in the dedup scan's `flagged` bitmask (in addition to
+    /// normal duplicate detection)
+    #[argh(option)]
+    dedup_blocklist: Option<String>,
+
+    /// filter predicate for hf:// streaming, e.g. "score > 0.9" or "lang == 'en' AND score >= 0.5";
+    /// row-groups proven by footer statistics to contain no matching rows are never fetched
+    #[argh(option, long = "where")]
+    where_clause: Option<String>,
+
+    /// comma-separated column names to project for hf:// streaming or
+    /// --parquet-lazy — only those columns are fetched/decoded
+    #[argh(option)]
+    columns: Option<String>,
+
+    /// keep a local Parquet file's row groups on disk and decode them lazily
+    /// on first access, instead of converting the whole file to JSONL up
+    /// front; makes opening huge Parquet files near-instant
+    #[argh(switch)]
+    parquet_lazy: bool,
+
+    /// bearer token for gated/private hf:// datasets (falls back to HF_TOKEN,
+    /// HUGGING_FACE_HUB_TOKEN, or the huggingface-cli login token file)
+    #[argh(option)]
+    token: Option<String>,
+
+    /// export the loaded dataset to this path, then exit (headless, no TUI);
+    /// format is inferred from the extension, or overridden with --export-format
+    #[argh(option)]
+    export: Option<String>,
+
+    /// export format: parquet, csv, jsonl (default: auto-detect from --export's extension)
+    #[argh(option, default = "String::from(\"auto\")")]
+    export_format: String,
+
     /// start MCP server on this port (exposes dataset as tools/resources to LLMs)
     #[argh(option, default = "0")]
     mcp_port: u16,
@@ -101,6 +190,41 @@ struct Args {
     /// run MCP server only (headless, no TUI)
     #[argh(switch)]
     mcp_only: bool,
+
+    /// run the MCP server over stdio instead of HTTP (headless, no TUI) -
+    /// what desktop clients like Claude Desktop expect when they launch
+    /// Caret as a subprocess rather than connecting over the network
+    #[argh(switch)]
+    mcp_stdio: bool,
+
+    /// speak JSON-RPC over stdio as an LSP server (invoke as `caret lsp --stdio`);
+    /// has no effect unless `file` is literally `lsp`
+    #[argh(switch)]
+    stdio: bool,
+}
+
+/// Build `CsvOptions` for `path` from the CLI args: starts from the
+/// extension-based dialect default (comma, or tab for `.tsv`) and applies
+/// any `--delimiter`/`--no-header`/`--csv-trim`/`--csv-comment` overrides.
+fn build_csv_options(args: &Args, path: &str) -> caret::format::CsvOptions {
+    let mut dialect = caret::format::CsvDialect::for_path(path);
+    if let Some(delim) = args.delimiter.as_deref().and_then(|s| s.bytes().next()) {
+        dialect.delimiter = delim;
+    }
+    if args.no_header {
+        dialect.has_header = false;
+    }
+    if args.csv_trim {
+        dialect.trim = true;
+    }
+    if let Some(comment) = args.csv_comment.as_deref().and_then(|s| s.bytes().next()) {
+        dialect.comment = Some(comment);
+    }
+
+    caret::format::CsvOptions {
+        typed: args.csv_typed,
+        dialect,
+    }
 }
 
 fn main() -> Result<()> {
@@ -115,18 +239,50 @@ fn main() -> Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    // Check if this is a HF streaming URL
+    // `caret lsp --stdio` is a separate headless mode: it never loads a
+    // dataset from `file`, it only lints whatever documents the editor opens.
+    if args.file == "lsp" {
+        if !args.stdio {
+            anyhow::bail!("caret lsp currently only supports --stdio");
+        }
+        return lsp::run_stdio();
+    }
+
+    // Check if this is a HF streaming URL, or a remote object storage URL
     let is_hf_stream = args.file.starts_with("hf://");
+    let is_object_store = caret::objectstore::is_object_store_url(&args.file);
 
     // Build the tokio runtime — used for MCP server and HF streaming.
     // We create it once and reuse it throughout the program lifetime.
     let rt = tokio::runtime::Runtime::new()
         .with_context(|| "Failed to create async runtime")?;
 
+    // Parse the --where predicate up front, if present, so a typo fails fast
+    // instead of after the stream connection is already open.
+    let predicate = args
+        .where_clause
+        .as_deref()
+        .map(Predicate::parse)
+        .transpose()
+        .with_context(|| "Failed to parse --where predicate")?;
+
+    let columns: Option<Vec<String>> = args
+        .columns
+        .as_deref()
+        .map(|s| s.split(',').map(|c| c.trim().to_string()).collect());
+
+    let token = resolve_hf_token(args.token.as_deref());
+
     // Load the dataset — either local file, stdin, or HF stream
     let dataset = if is_hf_stream {
         eprintln!("Streaming from {}...", args.file);
-        let (dataset, meta) = rt.block_on(streaming::open_hf_stream(&args.file))
+        let (dataset, meta) = rt
+            .block_on(streaming::open_hf_stream(
+                &args.file,
+                predicate.as_ref(),
+                columns.as_deref(),
+                token.as_deref(),
+            ))
             .with_context(|| format!("Failed to stream from {}", args.file))?;
         eprintln!(
             "Streamed {} lines ({}) — {} row-groups, {} columns",
@@ -136,6 +292,22 @@ fn main() -> Result<()> {
             meta.columns.len(),
         );
         dataset
+    } else if is_object_store {
+        eprintln!("Fetching {}...", args.file);
+        let csv_options = build_csv_options(&args, &args.file);
+        let dataset = rt
+            .block_on(caret::objectstore::open_object_store_dataset(
+                &args.file,
+                csv_options,
+            ))
+            .with_context(|| format!("Failed to fetch {}", args.file))?;
+        eprintln!(
+            "Loaded {} lines ({}) as {}",
+            dataset.line_count(),
+            dataset.size_human(),
+            dataset.format_name()
+        );
+        dataset
     } else if args.file == "-" {
         eprintln!("Reading from stdin...");
         let dataset = Dataset::from_stdin().with_context(|| "Failed to read from stdin")?;
@@ -161,8 +333,19 @@ fn main() -> Result<()> {
             InputFormat::Parquet => "Parquet",
             InputFormat::Csv => "CSV",
         });
-        let dataset = Dataset::open_with_format(&args.file, input_format)
-            .with_context(|| format!("Failed to open dataset: {}", args.file))?;
+        let csv_options = build_csv_options(&args, &args.file);
+        let parquet_options = caret::data::ParquetOptions {
+            lazy: args.parquet_lazy,
+            columns: columns.clone(),
+            ..Default::default()
+        };
+        let dataset = Dataset::open_with_format_and_options(
+            &args.file,
+            input_format,
+            csv_options,
+            parquet_options,
+        )
+        .with_context(|| format!("Failed to open dataset: {}", args.file))?;
         eprintln!(
             "Loaded {} lines ({}) as {}",
             dataset.line_count(),
@@ -177,9 +360,16 @@ fn main() -> Result<()> {
 
     // Load tokenizer if requested
     if let Some(ref tokenizer_path) = args.tokenizer_path {
-        // Custom tokenizer path takes priority
+        // Custom tokenizer path takes priority. A .gguf file carries its
+        // own embedded vocab/merges, so it's loaded through a different
+        // constructor than a plain tokenizer.json.
         eprintln!("Loading tokenizer from {}...", tokenizer_path);
-        match TokenizerWrapper::from_file(tokenizer_path) {
+        let result = if tokenizer_path.ends_with(".gguf") {
+            TokenizerWrapper::from_gguf(tokenizer_path)
+        } else {
+            TokenizerWrapper::from_file(tokenizer_path)
+        };
+        match result {
             Ok(tokenizer) => {
                 eprintln!("Tokenizer loaded: {}", tokenizer.name);
                 app = app.with_tokenizer(tokenizer);
@@ -190,12 +380,12 @@ fn main() -> Result<()> {
         }
     } else if args.tokenizer {
         // Determine tokenizer type from CLI
-        let tokenizer_type = TokenizerType::parse(&args.tokenizer_type)
+        let tokenizer_type = TokenizerType::from_str(&args.tokenizer_type)
             .unwrap_or(TokenizerType::Tiktoken);
 
         match tokenizer_type {
             TokenizerType::Tiktoken => {
-                let encoding = TiktokenEncoding::parse(&args.tiktoken_encoding)
+                let encoding = TiktokenEncoding::from_str(&args.tiktoken_encoding)
                     .unwrap_or(TiktokenEncoding::Cl100kBase);
                 eprintln!("Loading Tiktoken tokenizer ({:?})...", encoding);
                 match TokenizerWrapper::from_tiktoken(encoding) {
@@ -233,6 +423,42 @@ fn main() -> Result<()> {
                     }
                 }
             }
+            TokenizerType::Whitespace => {
+                eprintln!("Loading whitespace tokenizer...");
+                match TokenizerWrapper::from_whitespace() {
+                    Ok(tokenizer) => {
+                        eprintln!("Tokenizer loaded: {}", tokenizer.name);
+                        app = app.with_tokenizer(tokenizer);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to load whitespace tokenizer: {}", e);
+                    }
+                }
+            }
+            TokenizerType::UnicodeWords => {
+                eprintln!("Loading Unicode word tokenizer...");
+                match TokenizerWrapper::from_unicode_words() {
+                    Ok(tokenizer) => {
+                        eprintln!("Tokenizer loaded: {}", tokenizer.name);
+                        app = app.with_tokenizer(tokenizer);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to load Unicode word tokenizer: {}", e);
+                    }
+                }
+            }
+            TokenizerType::CharNgram { min, max } => {
+                eprintln!("Loading character n-gram tokenizer ({min}..={max})...");
+                match TokenizerWrapper::from_char_ngram(min, max) {
+                    Ok(tokenizer) => {
+                        eprintln!("Tokenizer loaded: {}", tokenizer.name);
+                        app = app.with_tokenizer(tokenizer);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to load n-gram tokenizer: {}", e);
+                    }
+                }
+            }
         }
     }
 
@@ -249,6 +475,11 @@ fn main() -> Result<()> {
         app = app.with_lint_results(results);
     }
 
+    // Export the dataset if requested (headless, no TUI)
+    if let Some(ref export_path) = args.export {
+        return run_export_mode(&args, &app.dataset, export_path);
+    }
+
     // Run fix mode if requested (headless, no TUI)
     if args.fix {
         return run_fix_mode(&args, &app.dataset);
@@ -273,7 +504,7 @@ fn main() -> Result<()> {
     // Initialize TUI (needed for both MCP and non-MCP paths)
     let tui = Tui::new()?;
 
-    if mcp_port > 0 || args.mcp_only {
+    if mcp_port > 0 || args.mcp_only || args.mcp_stdio {
         // Snapshot the dataset into an Arc for the async MCP server.
         // One-time copy cost — the server then holds a read-only reference.
         let dataset_arc = {
@@ -306,16 +537,37 @@ fn main() -> Result<()> {
         // Create command channel for MCP → TUI communication
         let (tui_tx, tui_rx) = command_channel();
 
-        if args.mcp_only {
+        if args.mcp_stdio {
+            // Headless stdio mode — what Claude Desktop/Cursor expect when
+            // they spawn Caret as a subprocess instead of connecting over HTTP.
+            eprintln!("Starting MCP server (stdio)...");
+            rt.block_on(mcp::start_mcp_server_with_transport(
+                mcp::Transport::Stdio,
+                dataset_arc,
+                dataset_path,
+                None,
+            ))?;
+            return Ok(());
+        } else if args.mcp_only {
             // Headless MCP-only mode — block on the server (no TUI commands)
             eprintln!("Starting MCP server (headless) on port {}...", port);
-            rt.block_on(mcp::start_mcp_server(dataset_arc, dataset_path, port, None))?;
+            rt.block_on(mcp::start_mcp_server_with_transport(
+                mcp::Transport::Http { port },
+                dataset_arc,
+                dataset_path,
+                None,
+            ))?;
             return Ok(());
         } else {
             // Background MCP server alongside the TUI
             eprintln!("Starting MCP server on port {}...", port);
-            rt.spawn(mcp::start_mcp_server(dataset_arc, dataset_path, port, Some(tui_tx)));
-            
+            rt.spawn(mcp::start_mcp_server_with_transport(
+                mcp::Transport::Http { port },
+                dataset_arc,
+                dataset_path,
+                Some(tui_tx),
+            ));
+
             // Store receiver for the TUI loop
             return run_tui_with_mcp(tui, app, tui_rx);
         }
@@ -356,7 +608,15 @@ fn run_tui_loop(mut tui: Tui, mut app: App, mut tui_rx: Option<TuiCommandReceive
                         app.should_quit = true;
                     }
 
-                    // Navigation
+                    // Navigation: in Tree mode, j/k move the tree cursor
+                    // among the current record's rows instead of scrolling
+                    // between dataset lines.
+                    (KeyCode::Char('j'), _) | (KeyCode::Down, _) if app.view_mode == ViewMode::Tree => {
+                        app.tree_cursor_down();
+                    }
+                    (KeyCode::Char('k'), _) | (KeyCode::Up, _) if app.view_mode == ViewMode::Tree => {
+                        app.tree_cursor_up();
+                    }
                     (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
                         app.scroll_down(1);
                     }
@@ -387,6 +647,12 @@ fn run_tui_loop(mut tui: Tui, mut app: App, mut tui_rx: Option<TuiCommandReceive
                         app.toggle_view_mode();
                     }
 
+                    // In Tree mode, Enter/Space expand or collapse the node
+                    // under the tree cursor instead of the detail panel.
+                    (KeyCode::Enter, _) | (KeyCode::Char(' '), _) if app.view_mode == ViewMode::Tree => {
+                        app.toggle_node_at_cursor();
+                    }
+
                     // Toggle detail panel
                     (KeyCode::Enter, _) => {
                         app.toggle_detail();
@@ -397,6 +663,21 @@ fn run_tui_loop(mut tui: Tui, mut app: App, mut tui_rx: Option<TuiCommandReceive
                         app.toggle_dedup();
                     }
 
+                    // Cycle theme (Shift+T)
+                    (KeyCode::Char('T'), _) => {
+                        app.cycle_theme();
+                    }
+
+                    // Toggle Markdown rendering in the detail panel (Shift+M)
+                    (KeyCode::Char('M'), _) => {
+                        app.toggle_markdown();
+                    }
+
+                    // Toggle ANSI escape rendering in the Text view and detail panel (Shift+A)
+                    (KeyCode::Char('A'), _) => {
+                        app.toggle_ansi_render();
+                    }
+
                     // Toggle help
                     (KeyCode::Char('?'), _) => {
                         app.show_help = !app.show_help;
@@ -456,24 +737,125 @@ fn apply_tui_command(app: &mut App, cmd: TuiCommand) {
         TuiCommand::GotoBottom => {
             app.goto_bottom();
         }
+        TuiCommand::GetCurrentLine(reply) => {
+            let _ = reply.send(serde_json::json!({
+                "line": app.selected_line,
+                "content": app.current_line_content(),
+                "is_duplicate": app.line_is_duplicate(app.selected_line),
+                "has_lint_error": app.line_has_error(app.selected_line),
+            }));
+        }
+        TuiCommand::GetLintErrorsForLine(line, reply) => {
+            let errors: Vec<serde_json::Value> = app
+                .lint_results
+                .iter()
+                .filter(|r| r.line == line)
+                .map(|r| {
+                    serde_json::json!({
+                        "message": r.error.message(),
+                        "severity": r.error.severity(),
+                        "code": r.error.code(),
+                    })
+                })
+                .collect();
+            let _ = reply.send(serde_json::json!({ "line": line, "errors": errors }));
+        }
+        TuiCommand::GetDedupClusters(reply) => {
+            let payload = match &app.dedup_result {
+                Some(dr) => serde_json::json!({
+                    "has_scan": true,
+                    "total_lines": dr.total_lines,
+                    "unique_count": dr.unique_count,
+                    "duplicate_count": dr.duplicate_count,
+                    "strategy": dr.strategy.to_string(),
+                    "canonical_map": dr.canonical_map,
+                }),
+                None => serde_json::json!({ "has_scan": false }),
+            };
+            let _ = reply.send(payload);
+        }
+        TuiCommand::GetViewMode(reply) => {
+            let _ = reply.send(serde_json::json!({ "view_mode": app.view_mode.label() }));
+        }
+        TuiCommand::ExpandNode(path) => {
+            app.expand_node(path);
+        }
+        TuiCommand::CollapseNode(path) => {
+            app.collapse_node(&path);
+        }
+        TuiCommand::ToggleNodeAtCursor => {
+            app.toggle_node_at_cursor();
+        }
     }
 }
 
+/// Run export mode (headless, no TUI): write the loaded dataset out to
+/// `export_path` as Parquet, CSV, or JSONL.
+fn run_export_mode(args: &Args, dataset: &Dataset, export_path: &str) -> Result<()> {
+    let format = if args.export_format == "auto" {
+        InputFormat::detect(export_path)
+    } else {
+        InputFormat::parse(&args.export_format).unwrap_or_else(|| {
+            eprintln!(
+                "Warning: Unknown export format '{}', using auto-detect",
+                args.export_format
+            );
+            InputFormat::detect(export_path)
+        })
+    };
+
+    eprintln!(
+        "Exporting {} lines to {} as {:?}...",
+        dataset.line_count(),
+        export_path,
+        format
+    );
+    dataset
+        .export(export_path, format, None)
+        .with_context(|| format!("Failed to export dataset to {}", export_path))?;
+    eprintln!("Export complete.");
+    Ok(())
+}
+
 /// Run dedup mode (headless, no TUI)
 fn run_dedup_mode(args: &Args, dataset: &Dataset) -> Result<()> {
     let strategy = match args.dedup_strategy.as_str() {
         "exact" => DedupStrategy::Exact,
+        "exact_strong" => DedupStrategy::ExactStrong,
+        "semantic" => DedupStrategy::Semantic {
+            threshold: args.dedup_semantic_threshold,
+        },
+        "weighted_simhash" => DedupStrategy::WeightedSimHash {
+            threshold: args.dedup_threshold,
+        },
         _ => DedupStrategy::SimHash {
             threshold: args.dedup_threshold,
+            fingerprint_bits: args.dedup_fingerprint_bits,
         },
     };
 
     eprintln!("Running dedup scan ({})...", strategy);
-    let engine = DedupEngine::new(strategy);
+    let mut engine = DedupEngine::new(strategy);
+    if matches!(strategy, DedupStrategy::Semantic { .. }) {
+        let endpoint = args
+            .dedup_embedding_endpoint
+            .as_deref()
+            .context("--dedup-strategy semantic requires --dedup-embedding-endpoint")?;
+        engine = engine.with_embedder(Arc::new(HttpEmbedder::new(endpoint, SEMANTIC_EMBEDDING_DIM)));
+    }
+    if let Some(ref blocklist) = args.dedup_blocklist {
+        let patterns: Vec<String> = blocklist.split(',').map(|s| s.trim().to_string()).collect();
+        engine = engine
+            .with_blocklist(&patterns)
+            .context("Failed to build --dedup-blocklist automaton")?;
+    }
     let result = engine.scan(dataset);
 
     eprintln!("\nDedup Results:");
     eprintln!("   {}", result.summary());
+    if result.flagged_count > 0 {
+        eprintln!("   {} lines flagged by blocklist", result.flagged_count);
+    }
 
     // Export deduplicated dataset if requested
     if let Some(ref export_path) = args.dedup_export {
@@ -530,8 +912,13 @@ fn run_fix_mode(args: &Args, dataset: &Dataset) -> Result<()> {
         output_path.clone()
     };
 
-    let fixer = Fixer::new();
+    let fixer = if args.fix_unsafe {
+        Fixer::new().with_min_applicability(Applicability::Unsafe)
+    } else {
+        Fixer::new()
+    };
     let mut summary = FixSummary::new();
+    summary.set_active_rules(fixer.active_rules());
 
     // Open output file
     let file = File::create(&temp_path)
@@ -541,10 +928,24 @@ fn run_fix_mode(args: &Args, dataset: &Dataset) -> Result<()> {
     // Process each line
     for i in 0..dataset.line_count() {
         if let Some(line) = dataset.get_line(i) {
-            match fixer.fix_line(line) {
-                FixResult::Fixed { line: fixed, fixes } => {
+            let result = fixer.fix_line(line);
+            if args.diff {
+                if let FixResult::Fixed { .. } = &result {
+                    summary.record_diff(i + 1, result.diff(line));
+                }
+            }
+            match result {
+                FixResult::Fixed { line: fixed, applied, suggested, conflicts, .. } => {
                     writeln!(writer, "{}", fixed)?;
-                    summary.record_fixed(&fixes);
+                    for conflict in &conflicts {
+                        eprintln!("Line {}: {}", i + 1, conflict.description());
+                    }
+                    if !suggested.is_empty() && !args.fix_unsafe {
+                        for fix in &suggested {
+                            eprintln!("Line {}: {} (suggested, not applied - pass --fix-unsafe to apply)", i + 1, fix.description());
+                        }
+                    }
+                    summary.record_fixed(&applied, &suggested, &conflicts);
                 }
                 FixResult::Unchanged(line) => {
                     writeln!(writer, "{}", line)?;
@@ -574,11 +975,23 @@ fn run_fix_mode(args: &Args, dataset: &Dataset) -> Result<()> {
 
     // Print summary
     eprintln!("\nFix Summary:");
+    eprintln!(
+        "   Active rules:    {}",
+        summary.active_rules.iter().map(|fix_type| fix_type.description()).collect::<Vec<_>>().join(", ")
+    );
     eprintln!("   Total lines:     {}", summary.total_lines);
     eprintln!("   Fixed lines:     {}", summary.fixed_lines);
     eprintln!("   Unchanged lines: {}", summary.unchanged_lines);
     eprintln!("   Skipped lines:   {}", summary.skipped_lines);
 
+    if args.diff {
+        if !summary.diffs.is_empty() {
+            eprintln!("\n{}", summary.render_diffs());
+        }
+        eprintln!("\nOutput written to: {}", output_path);
+        return Ok(());
+    }
+
     if !summary.fixes_by_type.is_empty() {
         eprintln!("\nFixes applied:");
         for (fix_type, count) in &summary.fixes_by_type {
@@ -586,6 +999,17 @@ fn run_fix_mode(args: &Args, dataset: &Dataset) -> Result<()> {
         }
     }
 
+    if !summary.suggested_by_type.is_empty() {
+        eprintln!("\nUnsafe fixes suggested but not applied (use --fix-unsafe to apply):");
+        for (fix_type, count) in &summary.suggested_by_type {
+            eprintln!("   {} x{}", fix_type, count);
+        }
+    }
+
+    if summary.conflicting_fixes > 0 {
+        eprintln!("\nConflicting fixes dropped: {}", summary.conflicting_fixes);
+    }
+
     eprintln!("\nOutput written to: {}", output_path);
 
     Ok(())
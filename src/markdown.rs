@@ -0,0 +1,211 @@
+//! Caret - Markdown rendering for the detail panel
+//!
+//! LLM training records overwhelmingly carry Markdown inside string fields
+//! such as `content`, `instruction`, or `response`. This module renders
+//! such a field as styled Ratatui lines the way an editor renders hover
+//! docs, instead of a wall of escaped `\n` inside raw JSON: headings,
+//! fenced/inline code, emphasis, lists, and block quotes, all styled from
+//! the active [`Theme`].
+
+use crate::theme::Theme;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Inline emphasis state accumulated while walking a paragraph/heading/item.
+#[derive(Default, Clone, Copy)]
+struct InlineStyle {
+    bold: bool,
+    italic: bool,
+    code: bool,
+}
+
+/// Render a single Markdown-formatted string field as styled lines.
+pub fn render_markdown(text: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut current: Vec<Span<'static>> = Vec::new();
+    let mut style_stack: Vec<InlineStyle> = vec![InlineStyle::default()];
+    let mut quote_depth = 0usize;
+    // One entry per open list; `Some(n)` is the next ordered item number,
+    // `None` means a bullet list.
+    let mut list_stack: Vec<Option<u64>> = Vec::new();
+    let mut in_code_block = false;
+
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                let depth = heading_depth(level);
+                current.push(Span::styled(
+                    format!("{} ", "#".repeat(depth)),
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                ));
+                style_stack.push(InlineStyle {
+                    bold: true,
+                    italic: false,
+                    code: false,
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                lines.push(Line::from(""));
+                style_stack.pop();
+            }
+            Event::Start(Tag::Paragraph) => {}
+            Event::End(TagEnd::Paragraph) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                lines.push(Line::from(""));
+            }
+            Event::Start(Tag::BlockQuote(_)) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                quote_depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                quote_depth = quote_depth.saturating_sub(1);
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                in_code_block = true;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    if !lang.is_empty() {
+                        lines.push(Line::from(Span::styled(
+                            format!("  {}", lang),
+                            Style::default().fg(theme.muted),
+                        )));
+                    }
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                in_code_block = false;
+            }
+            Event::Start(Tag::List(start)) => {
+                list_stack.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                list_stack.pop();
+            }
+            Event::Start(Tag::Item) => {
+                let indent = "  ".repeat(list_stack.len().saturating_sub(1));
+                let marker = match list_stack.last_mut() {
+                    Some(Some(n)) => {
+                        let label = format!("{}. ", n);
+                        *n += 1;
+                        label
+                    }
+                    _ => "• ".to_string(),
+                };
+                current.push(Span::raw(format!("{}{}", indent, marker)));
+            }
+            Event::End(TagEnd::Item) => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+            }
+            Event::Start(Tag::Emphasis) => {
+                let mut s = *style_stack.last().unwrap();
+                s.italic = true;
+                style_stack.push(s);
+            }
+            Event::End(TagEnd::Emphasis) => {
+                style_stack.pop();
+            }
+            Event::Start(Tag::Strong) => {
+                let mut s = *style_stack.last().unwrap();
+                s.bold = true;
+                style_stack.push(s);
+            }
+            Event::End(TagEnd::Strong) => {
+                style_stack.pop();
+            }
+            Event::Code(code) => {
+                current.push(Span::styled(
+                    format!(" {} ", code),
+                    Style::default().fg(theme.warning).bg(theme.muted),
+                ));
+            }
+            Event::Text(text) => {
+                if in_code_block {
+                    for (i, code_line) in text.split('\n').enumerate() {
+                        if i > 0 {
+                            flush_line(&mut current, &mut lines, quote_depth, theme);
+                        }
+                        current.push(Span::styled(
+                            code_line.to_string(),
+                            Style::default().fg(theme.fg).bg(theme.muted),
+                        ));
+                    }
+                } else {
+                    let style = inline_style(*style_stack.last().unwrap(), theme);
+                    current.push(Span::styled(text.to_string(), style));
+                }
+            }
+            Event::SoftBreak => {
+                current.push(Span::raw(" "));
+            }
+            Event::HardBreak => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+            }
+            Event::Rule => {
+                flush_line(&mut current, &mut lines, quote_depth, theme);
+                lines.push(Line::from(Span::styled(
+                    "─".repeat(40),
+                    Style::default().fg(theme.border),
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    flush_line(&mut current, &mut lines, quote_depth, theme);
+    lines
+}
+
+fn heading_depth(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn inline_style(s: InlineStyle, theme: &Theme) -> Style {
+    let mut style = Style::default().fg(theme.fg);
+    if s.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if s.italic {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if s.code {
+        style = style.fg(theme.warning).bg(theme.muted);
+    }
+    style
+}
+
+/// Push the accumulated spans as a finished line (prefixed with the active
+/// block-quote bar, if any) and reset the buffer. A no-op if the buffer is
+/// empty (whatever the quote depth), so blank Markdown lines don't pile up.
+fn flush_line(
+    current: &mut Vec<Span<'static>>,
+    lines: &mut Vec<Line<'static>>,
+    quote_depth: usize,
+    theme: &Theme,
+) {
+    if current.is_empty() {
+        return;
+    }
+    let mut spans = Vec::new();
+    if quote_depth > 0 {
+        spans.push(Span::styled(
+            "│ ".repeat(quote_depth),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    spans.append(current);
+    lines.push(Line::from(spans));
+}
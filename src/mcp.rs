@@ -16,6 +16,37 @@
 //! | `tools/call`                  | Execute a tool (e.g. `search_dataset`)     |
 //! | `resources/list`              | Enumerate exposed dataset resources        |
 //! | `resources/read`              | Read a specific resource (line range)      |
+//! | `resources/subscribe`         | Ask to be pushed updates for a resource    |
+//! | `resources/unsubscribe`       | Stop receiving updates for a resource      |
+//!
+//! HTTP POST can't carry server-initiated messages, so pushes
+//! (`notifications/resources/updated` / `notifications/resources/list_changed`)
+//! go out over a separate `GET /events` Server-Sent Events stream. A client
+//! opens that first to get a `clientId`, then passes it in `resources/subscribe`.
+//!
+//! `resources/read` and the `get_lines` tool page through large datasets via
+//! an opaque `cursor` string: pass the previous result's `nextCursor` back in
+//! to fetch the next page, and stop once `nextCursor` is absent. `tools/list`
+//! and `resources/list` accept the same `cursor` parameter for forward
+//! compatibility but currently only ever have one page, so they always
+//! return `nextCursor: null`.
+//!
+//! # Inspecting TUI state
+//!
+//! `get_current_line`, `get_lint_errors_for_line`, `get_dedup_clusters`, and
+//! `get_view_mode` let a tool call read back what the TUI is currently
+//! showing, rather than only driving it blind — each sends a
+//! [`crate::commands::TuiCommand`] query variant through the same channel
+//! used for navigation commands and awaits its `oneshot` reply. They return
+//! a `-32000` error if no TUI is attached (`--mcp-only` mode) or if the TUI
+//! doesn't reply within a few seconds.
+//!
+//! `expand_node`, `collapse_node`, and `toggle_node_at_cursor` let a tool call
+//! fold `ViewMode::Tree` nodes by JSON path (or by cursor position) the same
+//! way the j/k/Enter/Space keybindings do interactively. Unlike the `Get*`
+//! queries above, these are one-way commands with nothing to reply with, so
+//! they return as soon as the command is handed to the TUI's channel rather
+//! than waiting for it to be applied.
 //!
 //! # Architecture
 //!
@@ -26,21 +57,30 @@
 //! └──────────────┘              └──────────────┘                └──────────┘
 //! ```
 
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use anyhow::Result;
 use axum::{
     extract::State,
     http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine as _;
+use futures::stream::{self, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::AbortHandle;
 use tower_http::cors::CorsLayer;
 use tracing::info;
 
+use crate::commands::{TuiCommand, TuiCommandSender};
 use crate::data::Dataset;
 use crate::engine::{DedupEngine, DedupStrategy};
 
@@ -171,12 +211,110 @@ struct ContentBlock {
 pub struct McpState {
     pub dataset: Arc<Dataset>,
     pub dataset_path: String,
+    /// Abort handles for in-flight `tools/call` tasks, keyed by the
+    /// JSON-RPC request id (serialized to a string) that started them —
+    /// lets a `notifications/cancelled` message abort the matching task.
+    /// Guarded by its own lock rather than the outer `RwLock` so cancelling
+    /// a call never waits on a concurrent dataset read.
+    pub in_flight: Mutex<HashMap<String, AbortHandle>>,
+    /// Resource URIs each connected SSE client has subscribed to, keyed by
+    /// the `ClientId` handed out when it opens `GET /events`.
+    subscriptions: Mutex<HashMap<ClientId, HashSet<String>>>,
+    /// Next id to assign to a connecting SSE client.
+    next_client_id: AtomicU64,
+    /// Broadcasts resource change events to every connected SSE client;
+    /// each client's stream filters `Updated` events against its own
+    /// subscription set before forwarding them.
+    events: broadcast::Sender<ResourceEvent>,
+    /// Sender half of the TUI command channel — `None` in headless
+    /// (`--mcp-only`) mode, where there's no TUI event loop to receive
+    /// commands or fulfill queries. `Get*` tools return an error in that
+    /// case instead of hanging.
+    tui_tx: Option<TuiCommandSender>,
+}
+
+impl McpState {
+    fn new(dataset: Arc<Dataset>, dataset_path: String, tui_tx: Option<TuiCommandSender>) -> Self {
+        // Capacity just needs to outrun how fast a slow client can fall
+        // behind between polls; a lagging receiver skips ahead rather than
+        // blocking the publisher, which is fine for advisory change pushes.
+        let (events, _) = broadcast::channel(64);
+        Self {
+            dataset,
+            dataset_path,
+            in_flight: Mutex::new(HashMap::new()),
+            subscriptions: Mutex::new(HashMap::new()),
+            next_client_id: AtomicU64::new(0),
+            events,
+            tui_tx,
+        }
+    }
+
+    /// Publish `notifications/resources/updated` to every client subscribed
+    /// to `uri` — call after the dataset behind `uri` is reloaded so
+    /// clients don't keep serving a stale `resources/read` snapshot.
+    pub fn notify_resource_updated(&self, uri: &str) {
+        let _ = self.events.send(ResourceEvent::Updated { uri: uri.to_string() });
+    }
+
+    /// Publish `notifications/resources/list_changed` to every connected
+    /// client — call when the set of exposed datasets changes.
+    pub fn notify_resources_list_changed(&self) {
+        let _ = self.events.send(ResourceEvent::ListChanged);
+    }
+}
+
+/// Id assigned to an SSE connection when it opens `GET /events`, used to key
+/// its subscription set.
+type ClientId = u64;
+
+/// Item type of the `GET /events` stream — infallible since there's no way
+/// for an in-process broadcast receive to fail in a way worth surfacing to
+/// the client as a malformed SSE frame.
+type SseEvent = Result<Event, std::convert::Infallible>;
+
+/// A server-pushed resource change, broadcast to every SSE-connected client.
+#[derive(Debug, Clone)]
+enum ResourceEvent {
+    /// A subscribed resource's contents changed — only forwarded to clients
+    /// subscribed to `uri`.
+    Updated { uri: String },
+    /// The set of exposed resources changed — forwarded to every client.
+    ListChanged,
 }
 
 pub type SharedMcpState = Arc<RwLock<McpState>>;
 
+/// How the MCP server is exposed to a client.
+pub enum Transport {
+    /// Axum HTTP listener — the original transport, used by clients that
+    /// connect over the network.
+    Http { port: u16 },
+    /// Newline-delimited JSON-RPC over stdin/stdout — what desktop clients
+    /// (Claude Desktop, Cursor) expect when they spawn the server as a
+    /// child process instead of connecting over HTTP.
+    Stdio,
+}
+
 // ─── Server bootstrap ──────────────────────────────────────────────────────
 
+/// Start the MCP server over whichever `transport` the caller picked.
+///
+/// `tui_tx` is only honored for `Transport::Http` - a desktop client that
+/// spawns `caret --mcp-stdio` as a subprocess has no TUI on the other end
+/// of the channel to talk back to.
+pub async fn start_mcp_server_with_transport(
+    transport: Transport,
+    dataset: Arc<Dataset>,
+    dataset_path: String,
+    tui_tx: Option<TuiCommandSender>,
+) -> Result<()> {
+    match transport {
+        Transport::Http { port } => start_mcp_server(dataset, dataset_path, port, tui_tx).await,
+        Transport::Stdio => start_mcp_server_stdio(dataset, dataset_path).await,
+    }
+}
+
 /// Start the MCP server on the given port.
 ///
 /// Returns a `JoinHandle` — the caller can `.abort()` it for clean shutdown.
@@ -186,15 +324,15 @@ pub async fn start_mcp_server(
     dataset: Arc<Dataset>,
     dataset_path: String,
     port: u16,
+    tui_tx: Option<TuiCommandSender>,
 ) -> Result<()> {
-    let state: SharedMcpState = Arc::new(RwLock::new(McpState {
-        dataset,
-        dataset_path,
-    }));
+    let state: SharedMcpState =
+        Arc::new(RwLock::new(McpState::new(dataset, dataset_path, tui_tx)));
 
     let app = Router::new()
         .route("/", post(handle_jsonrpc))
         .route("/health", get(health_check))
+        .route("/events", get(sse_events))
         .layer(CorsLayer::permissive())
         .with_state(state);
 
@@ -206,6 +344,58 @@ pub async fn start_mcp_server(
     Ok(())
 }
 
+/// Start the MCP server speaking newline-delimited JSON-RPC over
+/// stdin/stdout — one JSON-RPC message per line, UTF-8, no Content-Length
+/// header, matching what desktop MCP clients expect from a spawned child
+/// process.
+///
+/// The caller must already have `tracing` configured to write to stderr
+/// (as `main` does) before calling this — any stray bytes on stdout would
+/// corrupt the protocol stream, since stdout carries JSON-RPC responses
+/// only.
+pub async fn start_mcp_server_stdio(dataset: Arc<Dataset>, dataset_path: String) -> Result<()> {
+    let state: SharedMcpState = Arc::new(RwLock::new(McpState::new(dataset, dataset_path, None)));
+
+    info!("MCP server listening on stdio");
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let req: JsonRpcRequest = match serde_json::from_str(&line) {
+            Ok(req) => req,
+            Err(e) => {
+                let response = JsonRpcResponse::error(None, -32700, format!("Parse error: {}", e));
+                write_jsonrpc_line(&mut stdout, &response).await?;
+                continue;
+            }
+        };
+
+        if let Some(response) = dispatch(req, &state).await {
+            write_jsonrpc_line(&mut stdout, &response).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Serialize a response and write it to stdout as a single line, flushing
+/// immediately so the client sees it without buffering delay.
+async fn write_jsonrpc_line(
+    stdout: &mut tokio::io::Stdout,
+    response: &JsonRpcResponse,
+) -> Result<()> {
+    let mut bytes = serde_json::to_vec(response)?;
+    bytes.push(b'\n');
+    stdout.write_all(&bytes).await?;
+    stdout.flush().await?;
+    Ok(())
+}
+
 /// Health-check endpoint (useful for readiness probes).
 async fn health_check() -> impl IntoResponse {
     (StatusCode::OK, Json(serde_json::json!({"status": "ok", "server": "caret-mcp"})))
@@ -213,28 +403,193 @@ async fn health_check() -> impl IntoResponse {
 
 // ─── JSON-RPC dispatcher ───────────────────────────────────────────────────
 
+/// Accepts either a single JSON-RPC object or a JSON array (batch request),
+/// mirroring the batching mature JSON-RPC servers support for clients that
+/// pipeline several calls — e.g. `tools/list` + `tools/call` — in one round
+/// trip.
 async fn handle_jsonrpc(
     State(state): State<SharedMcpState>,
-    Json(req): Json<JsonRpcRequest>,
+    Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
+    match body {
+        serde_json::Value::Array(items) => {
+            let mut responses = Vec::new();
+            for item in items {
+                match serde_json::from_value::<JsonRpcRequest>(item) {
+                    Ok(req) => responses.extend(dispatch(req, &state).await),
+                    Err(e) => responses.push(JsonRpcResponse::error(
+                        None,
+                        -32700,
+                        format!("Parse error: {}", e),
+                    )),
+                }
+            }
+
+            if responses.is_empty() {
+                StatusCode::NO_CONTENT.into_response()
+            } else {
+                Json(responses).into_response()
+            }
+        }
+        single => match serde_json::from_value::<JsonRpcRequest>(single) {
+            Ok(req) => match dispatch(req, &state).await {
+                Some(response) => Json(response).into_response(),
+                None => StatusCode::NO_CONTENT.into_response(),
+            },
+            Err(e) => Json(JsonRpcResponse::error(
+                None,
+                -32700,
+                format!("Parse error: {}", e),
+            ))
+            .into_response(),
+        },
+    }
+}
+
+/// Route a request through to its method handler, shared by both the HTTP
+/// and stdio transports. Returns `None` for notifications — any request
+/// with no `id`, such as `initialized` or `notifications/cancelled` — which
+/// must produce no response at all.
+async fn dispatch(req: JsonRpcRequest, state: &SharedMcpState) -> Option<JsonRpcResponse> {
+    let is_notification = req.id.is_none();
+
     let response = match req.method.as_str() {
-        "initialize" => handle_initialize(req.id),
+        "initialize" => handle_initialize(req.id.clone()),
         "initialized" => {
-            // Notification — no response required, but we reply with empty success
-            JsonRpcResponse::success(req.id, serde_json::json!({}))
+            // Notification — no response required, but we reply with empty
+            // success on the rare HTTP client that ignores the spec and
+            // sends an `id` anyway.
+            JsonRpcResponse::success(req.id.clone(), serde_json::json!({}))
+        }
+        "notifications/cancelled" => {
+            handle_cancel(req.params, state).await;
+            JsonRpcResponse::success(req.id.clone(), serde_json::json!({}))
         }
-        "tools/list" => handle_tools_list(req.id),
-        "tools/call" => handle_tools_call(req.id, req.params, &state).await,
-        "resources/list" => handle_resources_list(req.id, &state).await,
-        "resources/read" => handle_resources_read(req.id, req.params, &state).await,
+        "tools/list" => handle_tools_list(req.id.clone(), req.params.clone()),
+        "tools/call" => handle_tools_call(req.id.clone(), req.params, state).await,
+        "resources/list" => handle_resources_list(req.id.clone(), req.params.clone(), state).await,
+        "resources/read" => handle_resources_read(req.id.clone(), req.params, state).await,
+        "resources/subscribe" => handle_resources_subscribe(req.id.clone(), req.params, state).await,
+        "resources/unsubscribe" => handle_resources_unsubscribe(req.id.clone(), req.params, state).await,
         _ => JsonRpcResponse::error(
-            req.id,
+            req.id.clone(),
             -32601,
             format!("Method not found: {}", req.method),
         ),
     };
 
-    Json(response)
+    if is_notification {
+        None
+    } else {
+        Some(response)
+    }
+}
+
+/// Canonical registry key for a JSON-RPC request id — just its JSON
+/// representation, so a number `5` and a string `"5"` are tracked as
+/// distinct in-flight requests, matching how the id is compared on the wire.
+fn request_id_key(id: &serde_json::Value) -> String {
+    id.to_string()
+}
+
+/// Page size used by `resources/read` and `get_lines` when paginating via
+/// `cursor`/`nextCursor`.
+const PAGINATION_PAGE_SIZE: usize = 100;
+
+/// Encode a line offset as an opaque pagination cursor. Callers must treat
+/// the result as opaque — only `decode_cursor` is meant to read it back.
+fn encode_cursor(offset: usize) -> String {
+    BASE64_STANDARD.encode(offset.to_string())
+}
+
+/// Decode a cursor produced by `encode_cursor` back into a line offset.
+/// Returns `None` for a missing, malformed, or non-numeric cursor so callers
+/// can fall back to starting from the beginning.
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    let bytes = BASE64_STANDARD.decode(cursor).ok()?;
+    String::from_utf8(bytes).ok()?.parse().ok()
+}
+
+/// Handle a `notifications/cancelled` message: abort the in-flight task
+/// registered under `params.requestId`, if any is still running.
+async fn handle_cancel(params: serde_json::Value, state: &SharedMcpState) {
+    let Some(request_id) = params.get("requestId") else {
+        return;
+    };
+    let key = request_id_key(request_id);
+
+    let handle = {
+        let state = state.read().await;
+        state.in_flight.lock().await.remove(&key)
+    };
+
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            info!("Cancelled in-flight request {}", key);
+        }
+        None => info!("Cancellation requested for unknown or already-finished request {}", key),
+    }
+}
+
+/// Run `f` on the blocking threadpool, registering its `AbortHandle` under
+/// `id` in `state`'s in-flight registry so a `notifications/cancelled`
+/// message can abort it, and deregistering once it completes. Aborting only
+/// stops a closure that has not started yet; one that is already running
+/// runs to completion. Either way a cancelled call returns the JSON-RPC
+/// error response `-32800` ("Request cancelled") rather than the task's own
+/// result.
+async fn run_cancellable_blocking<T, F>(
+    id: &Option<serde_json::Value>,
+    state: &SharedMcpState,
+    f: F,
+) -> Result<T, JsonRpcResponse>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let key = id.as_ref().map(request_id_key);
+    let task = tokio::task::spawn_blocking(f);
+
+    if let Some(key) = &key {
+        let state = state.read().await;
+        state
+            .in_flight
+            .lock()
+            .await
+            .insert(key.clone(), task.abort_handle());
+    }
+
+    let result = task.await;
+
+    // `handle_cancel` removes the entry when it aborts the task, so a
+    // missing entry here means the call was cancelled.
+    let cancelled = match &key {
+        Some(key) => {
+            let state = state.read().await;
+            state.in_flight.lock().await.remove(key).is_none()
+        }
+        None => false,
+    };
+
+    match result {
+        _ if cancelled => Err(JsonRpcResponse::error(
+            id.clone(),
+            -32800,
+            "Request cancelled".into(),
+        )),
+        Ok(value) => Ok(value),
+        Err(e) if e.is_cancelled() => Err(JsonRpcResponse::error(
+            id.clone(),
+            -32800,
+            "Request cancelled".into(),
+        )),
+        Err(e) => Err(JsonRpcResponse::error(
+            id.clone(),
+            -32603,
+            format!("Task join error: {}", e),
+        )),
+    }
 }
 
 // ─── Method handlers ────────────────────────────────────────────────────────
@@ -247,7 +602,9 @@ fn handle_initialize(id: Option<serde_json::Value>) -> JsonRpcResponse {
                 list_changed: false,
             },
             resources: ResourcesCapability {
-                list_changed: false,
+                // Resource subscriptions now push `notifications/resources/updated`
+                // and `notifications/resources/list_changed` over `GET /events`.
+                list_changed: true,
             },
         },
         server_info: ServerInfo {
@@ -259,7 +616,10 @@ fn handle_initialize(id: Option<serde_json::Value>) -> JsonRpcResponse {
     JsonRpcResponse::success(id, serde_json::to_value(result).expect("InitializeResult is serializable"))
 }
 
-fn handle_tools_list(id: Option<serde_json::Value>) -> JsonRpcResponse {
+fn handle_tools_list(id: Option<serde_json::Value>, _params: serde_json::Value) -> JsonRpcResponse {
+    // `_params.cursor` is accepted but unused — there's only ever one page of
+    // tools today. Plumbed through now so a future multi-dataset registry can
+    // paginate without another protocol-shaping change.
     let tools = vec![
         ToolDescriptor {
             name: "search_dataset".into(),
@@ -301,22 +661,29 @@ fn handle_tools_list(id: Option<serde_json::Value>) -> JsonRpcResponse {
         ToolDescriptor {
             name: "get_lines".into(),
             description: "Retrieve specific lines from the dataset by index range. \
-                          Supports O(1) random access via memory-mapped byte offsets."
+                          Supports O(1) random access via memory-mapped byte offsets. \
+                          For paging through a large dataset, pass the `nextCursor` from \
+                          a previous call back in as `cursor` instead of tracking `start` \
+                          yourself."
                 .into(),
             input_schema: serde_json::json!({
                 "type": "object",
                 "properties": {
                     "start": {
                         "type": "integer",
-                        "description": "Start line index (0-based)"
+                        "description": "Start line index (0-based). Ignored if `cursor` is set."
                     },
                     "count": {
                         "type": "integer",
                         "description": "Number of lines to retrieve (default: 10, max: 500)",
                         "default": 10
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Opaque cursor from a previous call's `nextCursor`, \
+                                        for resuming a paged read"
                     }
-                },
-                "required": ["start"]
+                }
             }),
         },
         ToolDescriptor {
@@ -329,23 +696,134 @@ fn handle_tools_list(id: Option<serde_json::Value>) -> JsonRpcResponse {
                 "properties": {
                     "strategy": {
                         "type": "string",
-                        "enum": ["exact", "simhash"],
-                        "description": "Dedup strategy (default: simhash)",
+                        "enum": ["exact", "exact_strong", "simhash", "weighted_simhash"],
+                        "description": "Dedup strategy. exact_strong uses a collision-free \
+                                        256-bit BLAKE3 digest instead of exact's 64-bit hash. \
+                                        weighted_simhash reweights each shingle by its inverse \
+                                        document frequency so boilerplate shingles don't \
+                                        dominate the fingerprint (default: simhash)",
                         "default": "simhash"
                     },
                     "threshold": {
                         "type": "integer",
                         "description": "SimHash Hamming distance threshold (default: 3)",
                         "default": 3
+                    },
+                    "fingerprint_bits": {
+                        "type": "integer",
+                        "enum": [64, 128],
+                        "description": "SimHash fingerprint width. 128 roughly doubles usable \
+                                        threshold resolution for long documents (default: 64)",
+                        "default": 64
+                    },
+                    "blocklist": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Substrings to flag in each line's content, independent \
+                                        of duplicate detection. Matches are reported as \
+                                        flagged_count in the result metadata."
                     }
                 }
             }),
         },
+        ToolDescriptor {
+            name: "get_current_line".into(),
+            description: "Query the line currently selected in the TUI — content, line \
+                          number, and duplicate/lint-error status. Requires a TUI to be \
+                          attached (not available in --mcp-only mode)."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolDescriptor {
+            name: "get_lint_errors_for_line".into(),
+            description: "Query lint errors found for a specific line, as last computed by \
+                          the TUI. Requires a TUI to be attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "line": {
+                        "type": "integer",
+                        "description": "Line index (0-based) to query lint errors for"
+                    }
+                },
+                "required": ["line"]
+            }),
+        },
+        ToolDescriptor {
+            name: "get_dedup_clusters".into(),
+            description: "Query the TUI's current dedup scan result (run interactively via \
+                          the Shift+D keybinding), if one exists. Requires a TUI to be \
+                          attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolDescriptor {
+            name: "get_view_mode".into(),
+            description: "Query the TUI's active view mode (TEXT / TOKEN X-RAY / TREE). \
+                          Requires a TUI to be attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolDescriptor {
+            name: "expand_node".into(),
+            description: "Expand the ViewMode::Tree node at a JSON path (e.g. \
+                          `messages[2].content`) in the TUI. Requires a TUI to be attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "JSON path of the node to expand, e.g. messages[2].content"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDescriptor {
+            name: "collapse_node".into(),
+            description: "Collapse the ViewMode::Tree node at a JSON path in the TUI. \
+                          Requires a TUI to be attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "JSON path of the node to collapse, e.g. messages[2].content"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        ToolDescriptor {
+            name: "toggle_node_at_cursor".into(),
+            description: "Toggle whichever ViewMode::Tree node currently has the TUI's tree \
+                          cursor. Requires a TUI to be attached."
+                .into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
     ];
 
     JsonRpcResponse::success(
         id,
-        serde_json::json!({ "tools": serde_json::to_value(&tools).expect("ToolDescriptor is serializable") }),
+        serde_json::json!({
+            "tools": serde_json::to_value(&tools).expect("ToolDescriptor is serializable"),
+            "nextCursor": serde_json::Value::Null,
+        }),
     )
 }
 
@@ -369,6 +847,13 @@ async fn handle_tools_call(
         "dataset_info" => tool_dataset_info(id, state).await,
         "get_lines" => tool_get_lines(id, arguments, state).await,
         "dedup_scan" => tool_dedup_scan(id, arguments, state).await,
+        "get_current_line" => tool_get_current_line(id, state).await,
+        "get_lint_errors_for_line" => tool_get_lint_errors_for_line(id, arguments, state).await,
+        "get_dedup_clusters" => tool_get_dedup_clusters(id, state).await,
+        "get_view_mode" => tool_get_view_mode(id, state).await,
+        "expand_node" => tool_expand_node(id, arguments, state).await,
+        "collapse_node" => tool_collapse_node(id, arguments, state).await,
+        "toggle_node_at_cursor" => tool_toggle_node_at_cursor(id, state).await,
         _ => JsonRpcResponse::error(
             id,
             -32602,
@@ -377,6 +862,146 @@ async fn handle_tools_call(
     }
 }
 
+/// Send a `TuiCommand` query built from `make_cmd` to the TUI event loop and
+/// await its reply, wrapping the result as a tool's JSON-RPC response.
+/// `make_cmd` takes the query's `oneshot::Sender` and returns the
+/// `TuiCommand` variant to send. Errors (no TUI attached, or the TUI
+/// dropped the reply without answering) become a JSON-RPC error response
+/// rather than hanging the caller.
+async fn query_tui(
+    id: Option<serde_json::Value>,
+    state: &SharedMcpState,
+    make_cmd: impl FnOnce(tokio::sync::oneshot::Sender<serde_json::Value>) -> TuiCommand,
+) -> JsonRpcResponse {
+    let tui_tx = state.read().await.tui_tx.clone();
+    let Some(tui_tx) = tui_tx else {
+        return JsonRpcResponse::error(
+            id,
+            -32000,
+            "No TUI attached to query (server is running in --mcp-only mode)".into(),
+        );
+    };
+
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    if tui_tx.send(make_cmd(reply_tx)).is_err() {
+        return JsonRpcResponse::error(id, -32000, "TUI event loop is no longer running".into());
+    }
+
+    // The TUI loop polls its command channel once per frame (~16ms), so a
+    // generous timeout distinguishes "a frame or two behind" from "the TUI
+    // has stopped responding" without making a stuck query hang forever.
+    match tokio::time::timeout(std::time::Duration::from_secs(5), reply_rx).await {
+        Ok(Ok(value)) => JsonRpcResponse::success(id, value),
+        Ok(Err(_)) => JsonRpcResponse::error(id, -32000, "TUI dropped the query without replying".into()),
+        Err(_) => JsonRpcResponse::error(id, -32000, "Timed out waiting for the TUI to reply".into()),
+    }
+}
+
+/// `get_current_line` — query the currently selected line in the TUI.
+async fn tool_get_current_line(id: Option<serde_json::Value>, state: &SharedMcpState) -> JsonRpcResponse {
+    let response = query_tui(id.clone(), state, TuiCommand::GetCurrentLine).await;
+    wrap_query_result(response)
+}
+
+/// `get_lint_errors_for_line` — query lint errors found for a specific line.
+async fn tool_get_lint_errors_for_line(
+    id: Option<serde_json::Value>,
+    args: serde_json::Value,
+    state: &SharedMcpState,
+) -> JsonRpcResponse {
+    let line = args.get("line").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+    let response = query_tui(id.clone(), state, |reply| TuiCommand::GetLintErrorsForLine(line, reply)).await;
+    wrap_query_result(response)
+}
+
+/// `get_dedup_clusters` — query the TUI's current dedup scan result, if any.
+async fn tool_get_dedup_clusters(id: Option<serde_json::Value>, state: &SharedMcpState) -> JsonRpcResponse {
+    let response = query_tui(id.clone(), state, TuiCommand::GetDedupClusters).await;
+    wrap_query_result(response)
+}
+
+/// `get_view_mode` — query the TUI's active view mode.
+async fn tool_get_view_mode(id: Option<serde_json::Value>, state: &SharedMcpState) -> JsonRpcResponse {
+    let response = query_tui(id.clone(), state, TuiCommand::GetViewMode).await;
+    wrap_query_result(response)
+}
+
+/// Wrap a successful `query_tui` JSON payload as a tool result's
+/// `content`/`metadata`; pass an error response through unchanged.
+fn wrap_query_result(response: JsonRpcResponse) -> JsonRpcResponse {
+    let Some(metadata) = response.result else {
+        return response;
+    };
+    let content = vec![ContentBlock {
+        content_type: "text".into(),
+        text: serde_json::to_string_pretty(&metadata).unwrap_or_else(|_| metadata.to_string()),
+    }];
+    JsonRpcResponse::success(
+        response.id,
+        serde_json::json!({
+            "content": serde_json::to_value(&content).expect("ContentBlock is serializable"),
+            "metadata": metadata,
+        }),
+    )
+}
+
+/// Send a one-way `TuiCommand` to the TUI event loop — no reply expected,
+/// just an acknowledgement that it was delivered. Errors the same way
+/// `query_tui` does when no TUI is attached.
+fn send_tui_command(id: Option<serde_json::Value>, state_tui_tx: &Option<TuiCommandSender>, cmd: TuiCommand) -> JsonRpcResponse {
+    let Some(tui_tx) = state_tui_tx else {
+        return JsonRpcResponse::error(
+            id,
+            -32000,
+            "No TUI attached to command (server is running in --mcp-only mode)".into(),
+        );
+    };
+    if tui_tx.send(cmd).is_err() {
+        return JsonRpcResponse::error(id, -32000, "TUI event loop is no longer running".into());
+    }
+
+    let content = vec![ContentBlock {
+        content_type: "text".into(),
+        text: "ok".into(),
+    }];
+    JsonRpcResponse::success(
+        id,
+        serde_json::json!({
+            "content": serde_json::to_value(&content).expect("ContentBlock is serializable"),
+        }),
+    )
+}
+
+/// `expand_node` — open a `ViewMode::Tree` node at a JSON path (e.g.
+/// `messages[2].content`) in the TUI.
+async fn tool_expand_node(
+    id: Option<serde_json::Value>,
+    args: serde_json::Value,
+    state: &SharedMcpState,
+) -> JsonRpcResponse {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let tui_tx = state.read().await.tui_tx.clone();
+    send_tui_command(id, &tui_tx, TuiCommand::ExpandNode(path))
+}
+
+/// `collapse_node` — close a `ViewMode::Tree` node at a JSON path in the TUI.
+async fn tool_collapse_node(
+    id: Option<serde_json::Value>,
+    args: serde_json::Value,
+    state: &SharedMcpState,
+) -> JsonRpcResponse {
+    let path = args.get("path").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let tui_tx = state.read().await.tui_tx.clone();
+    send_tui_command(id, &tui_tx, TuiCommand::CollapseNode(path))
+}
+
+/// `toggle_node_at_cursor` — toggle whichever `ViewMode::Tree` node
+/// currently has the TUI's tree cursor.
+async fn tool_toggle_node_at_cursor(id: Option<serde_json::Value>, state: &SharedMcpState) -> JsonRpcResponse {
+    let tui_tx = state.read().await.tui_tx.clone();
+    send_tui_command(id, &tui_tx, TuiCommand::ToggleNodeAtCursor)
+}
+
 /// `search_dataset` — regex search over the mmap'd dataset.
 ///
 /// Uses `regex::Regex` (which auto-selects SIMD acceleration on x86_64)
@@ -414,8 +1039,10 @@ async fn tool_search_dataset(
     drop(state_guard);
     let query_clone = query.clone();
 
-    // Offload CPU-intensive regex scan to blocking threadpool
-    let result = tokio::task::spawn_blocking(move || {
+    // Offload CPU-intensive regex scan to blocking threadpool, registered
+    // so a `notifications/cancelled` message can cancel the call. A scan
+    // that has already started still runs to the end.
+    let result = run_cancellable_blocking(&id, state, move || {
         search_dataset_impl(&dataset, &query_clone, max_results, context_lines)
     })
     .await;
@@ -449,7 +1076,7 @@ async fn tool_search_dataset(
             )
         }
         Ok(Err(e)) => JsonRpcResponse::error(id, -32603, format!("Search error: {}", e)),
-        Err(e) => JsonRpcResponse::error(id, -32603, format!("Task join error: {}", e)),
+        Err(cancelled_response) => cancelled_response,
     }
 }
 
@@ -532,16 +1159,21 @@ async fn tool_dataset_info(
     )
 }
 
-/// `get_lines` — O(1) random access to specific line ranges.
+/// `get_lines` — O(1) random access to specific line ranges. Accepts either
+/// an explicit `start` or a `cursor` carried over from a previous call's
+/// `nextCursor` — the cursor takes priority so a client can page through a
+/// dataset without re-deriving offsets itself.
 async fn tool_get_lines(
     id: Option<serde_json::Value>,
     args: serde_json::Value,
     state: &SharedMcpState,
 ) -> JsonRpcResponse {
     let start = args
-        .get("start")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(0) as usize;
+        .get("cursor")
+        .and_then(|v| v.as_str())
+        .and_then(decode_cursor)
+        .or_else(|| args.get("start").and_then(|v| v.as_u64()).map(|v| v as usize))
+        .unwrap_or(0);
     let count = args
         .get("count")
         .and_then(|v| v.as_u64())
@@ -551,8 +1183,9 @@ async fn tool_get_lines(
     let state = state.read().await;
     let ds = &state.dataset;
 
+    let end = (start + count).min(ds.line_count());
     let mut lines = Vec::new();
-    for i in start..(start + count).min(ds.line_count()) {
+    for i in start..end {
         if let Some(line) = ds.get_line(i) {
             lines.push(format!("L{}: {}", i + 1, line));
         }
@@ -561,13 +1194,7 @@ async fn tool_get_lines(
     let text = if lines.is_empty() {
         format!("No lines found at index {} (dataset has {} lines)", start, ds.line_count())
     } else {
-        format!(
-            "Lines {}-{} of {}:\n\n{}",
-            start + 1,
-            (start + count).min(ds.line_count()),
-            ds.line_count(),
-            lines.join("\n")
-        )
+        format!("Lines {}-{} of {}:\n\n{}", start + 1, end, ds.line_count(), lines.join("\n"))
     };
 
     let content = vec![ContentBlock {
@@ -575,10 +1202,14 @@ async fn tool_get_lines(
         text,
     }];
 
-    JsonRpcResponse::success(
-        id,
-        serde_json::json!({ "content": serde_json::to_value(&content).expect("ContentBlock is serializable") }),
-    )
+    let mut result = serde_json::json!({
+        "content": serde_json::to_value(&content).expect("ContentBlock is serializable"),
+    });
+    if end < ds.line_count() {
+        result["nextCursor"] = serde_json::json!(encode_cursor(end));
+    }
+
+    JsonRpcResponse::success(id, result)
 }
 
 /// `dedup_scan` — run the SIMD dedup engine and return results.
@@ -595,20 +1226,44 @@ async fn tool_dedup_scan(
         .get("threshold")
         .and_then(|v| v.as_u64())
         .unwrap_or(3) as u32;
+    let fingerprint_bits = args
+        .get("fingerprint_bits")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(64) as u32;
 
     let strategy = match strategy_str {
         "exact" => DedupStrategy::Exact,
-        _ => DedupStrategy::SimHash { threshold },
+        "exact_strong" => DedupStrategy::ExactStrong,
+        "weighted_simhash" => DedupStrategy::WeightedSimHash { threshold },
+        _ => DedupStrategy::SimHash {
+            threshold,
+            fingerprint_bits,
+        },
     };
+    let blocklist: Vec<String> = args
+        .get("blocklist")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut engine = DedupEngine::new(strategy);
+    if !blocklist.is_empty() {
+        engine = match engine.with_blocklist(&blocklist) {
+            Ok(engine) => engine,
+            Err(e) => return JsonRpcResponse::error(id, -32602, format!("Invalid blocklist: {}", e)),
+        };
+    }
 
-    let state = state.read().await;
-    let dataset = Arc::clone(&state.dataset);
+    let dataset = Arc::clone(&state.read().await.dataset);
 
-    let result = tokio::task::spawn_blocking(move || {
-        let engine = DedupEngine::new(strategy);
-        engine.scan(&dataset)
-    })
-    .await;
+    // Offload the dedup scan to the blocking threadpool, registered so a
+    // `notifications/cancelled` message can cancel the call. A scan that has
+    // already started still runs to the end.
+    let result = run_cancellable_blocking(&id, state, move || engine.scan(&dataset)).await;
 
     match result {
         Ok(dr) => {
@@ -634,12 +1289,14 @@ async fn tool_dedup_scan(
                  Total lines: {}\n\
                  Unique: {}\n\
                  Duplicates: {} ({:.1}%)\n\
+                 Flagged by blocklist: {}\n\
                  Scan time: {:.1}ms",
                 dr.strategy,
                 dr.total_lines,
                 dr.unique_count,
                 dr.duplicate_count,
                 dr.dedup_ratio() * 100.0,
+                dr.flagged_count,
                 dr.elapsed_us as f64 / 1000.0,
             );
 
@@ -657,21 +1314,26 @@ async fn tool_dedup_scan(
                         "unique_count": dr.unique_count,
                         "duplicate_count": dr.duplicate_count,
                         "dedup_ratio": dr.dedup_ratio(),
+                        "flagged_count": dr.flagged_count,
                         "elapsed_ms": dr.elapsed_us as f64 / 1000.0,
                         "sample_pairs": sample_pairs,
                     }
                 }),
             )
         }
-        Err(e) => JsonRpcResponse::error(id, -32603, format!("Dedup scan failed: {}", e)),
+        Err(cancelled_response) => cancelled_response,
     }
 }
 
 /// Handle `resources/list` — expose the loaded dataset as a resource.
 async fn handle_resources_list(
     id: Option<serde_json::Value>,
+    _params: serde_json::Value,
     state: &SharedMcpState,
 ) -> JsonRpcResponse {
+    // `_params.cursor` is accepted but unused — there's only ever one
+    // resource today. Plumbed through now so a future multi-dataset
+    // registry can paginate without another protocol-shaping change.
     let state = state.read().await;
 
     let resources = vec![ResourceDescriptor {
@@ -688,11 +1350,15 @@ async fn handle_resources_list(
 
     JsonRpcResponse::success(
         id,
-        serde_json::json!({ "resources": serde_json::to_value(&resources).expect("ResourceDescriptor is serializable") }),
+        serde_json::json!({
+            "resources": serde_json::to_value(&resources).expect("ResourceDescriptor is serializable"),
+            "nextCursor": serde_json::Value::Null,
+        }),
     )
 }
 
-/// Handle `resources/read` — return a slice of the dataset.
+/// Handle `resources/read` — return a page of the dataset, `PAGINATION_PAGE_SIZE`
+/// lines at a time, starting from `params.cursor` (or the beginning).
 async fn handle_resources_read(
     id: Option<serde_json::Value>,
     params: serde_json::Value,
@@ -702,14 +1368,18 @@ async fn handle_resources_read(
         .get("uri")
         .and_then(|v| v.as_str())
         .unwrap_or("");
+    let offset = params
+        .get("cursor")
+        .and_then(|v| v.as_str())
+        .and_then(decode_cursor)
+        .unwrap_or(0);
 
     let state = state.read().await;
     let ds = &state.dataset;
 
-    // Return first 100 lines as a preview
-    let preview_count = 100.min(ds.line_count());
-    let mut lines = Vec::with_capacity(preview_count);
-    for i in 0..preview_count {
+    let end = (offset + PAGINATION_PAGE_SIZE).min(ds.line_count());
+    let mut lines = Vec::with_capacity(end.saturating_sub(offset));
+    for i in offset..end {
         if let Some(line) = ds.get_line(i) {
             lines.push(line.to_string());
         }
@@ -723,12 +1393,165 @@ async fn handle_resources_read(
         "text": text,
     })];
 
-    JsonRpcResponse::success(
-        id,
-        serde_json::json!({ "contents": contents }),
+    let mut result = serde_json::json!({ "contents": contents });
+    if end < ds.line_count() {
+        result["nextCursor"] = serde_json::json!(encode_cursor(end));
+    }
+
+    JsonRpcResponse::success(id, result)
+}
+
+/// Handle `resources/subscribe` — record that `clientId` (assigned when it
+/// opened `GET /events`) wants `notifications/resources/updated` pushes for
+/// `uri`.
+async fn handle_resources_subscribe(
+    id: Option<serde_json::Value>,
+    params: serde_json::Value,
+    state: &SharedMcpState,
+) -> JsonRpcResponse {
+    let (Some(uri), Some(client_id)) = subscription_target(&params) else {
+        return JsonRpcResponse::error(
+            id,
+            -32602,
+            "Expected { uri: string, clientId: number } (clientId comes from GET /events)".into(),
+        );
+    };
+
+    let state = state.read().await;
+    state
+        .subscriptions
+        .lock()
+        .await
+        .entry(client_id)
+        .or_default()
+        .insert(uri.to_string());
+
+    JsonRpcResponse::success(id, serde_json::json!({}))
+}
+
+/// Handle `resources/unsubscribe` — the inverse of
+/// [`handle_resources_subscribe`].
+async fn handle_resources_unsubscribe(
+    id: Option<serde_json::Value>,
+    params: serde_json::Value,
+    state: &SharedMcpState,
+) -> JsonRpcResponse {
+    let (Some(uri), Some(client_id)) = subscription_target(&params) else {
+        return JsonRpcResponse::error(
+            id,
+            -32602,
+            "Expected { uri: string, clientId: number } (clientId comes from GET /events)".into(),
+        );
+    };
+
+    let state = state.read().await;
+    if let Some(subscribed) = state.subscriptions.lock().await.get_mut(&client_id) {
+        subscribed.remove(uri);
+    }
+
+    JsonRpcResponse::success(id, serde_json::json!({}))
+}
+
+/// Pull `{ uri, clientId }` out of a `resources/subscribe` /
+/// `resources/unsubscribe` params object.
+fn subscription_target(params: &serde_json::Value) -> (Option<&str>, Option<ClientId>) {
+    (
+        params.get("uri").and_then(|v| v.as_str()),
+        params.get("clientId").and_then(|v| v.as_u64()),
     )
 }
 
+/// `GET /events` — open a Server-Sent Events stream for server-pushed
+/// resource change notifications.
+///
+/// The first event (`event: ready`) carries the `clientId` this connection
+/// was assigned; the client must pass it as `clientId` in subsequent
+/// `resources/subscribe` / `resources/unsubscribe` calls over the regular
+/// JSON-RPC endpoint, since HTTP POST requests don't carry a connection
+/// identity of their own.
+async fn sse_events(
+    State(state): State<SharedMcpState>,
+) -> Sse<impl Stream<Item = SseEvent>> {
+    let client_id = {
+        let guard = state.read().await;
+        let id = guard.next_client_id.fetch_add(1, Ordering::Relaxed);
+        guard.subscriptions.lock().await.insert(id, HashSet::new());
+        id
+    };
+
+    let ready = stream::once({
+        let client_id = client_id;
+        async move {
+            let event: SseEvent = Ok(Event::default()
+                .event("ready")
+                .data(serde_json::json!({ "clientId": client_id }).to_string()));
+            event
+        }
+    });
+
+    let rx = state.read().await.events.subscribe();
+    let updates = stream::unfold(
+        (rx, Arc::clone(&state), client_id, ClientGuard { state: Arc::clone(&state), client_id }),
+        move |(mut rx, state, client_id, guard)| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(ResourceEvent::ListChanged) => {
+                        let notification = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "method": "notifications/resources/list_changed",
+                        });
+                        let event: SseEvent = Ok(Event::default().data(notification.to_string()));
+                        return Some((event, (rx, state, client_id, guard)));
+                    }
+                    Ok(ResourceEvent::Updated { uri }) => {
+                        let subscribed = state
+                            .read()
+                            .await
+                            .subscriptions
+                            .lock()
+                            .await
+                            .get(&client_id)
+                            .is_some_and(|set| set.contains(&uri));
+                        if !subscribed {
+                            continue;
+                        }
+                        let notification = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "method": "notifications/resources/updated",
+                            "params": { "uri": uri },
+                        });
+                        let event: SseEvent = Ok(Event::default().data(notification.to_string()));
+                        return Some((event, (rx, state, client_id, guard)));
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                }
+            }
+        },
+    );
+
+    Sse::new(ready.chain(updates)).keep_alive(KeepAlive::default())
+}
+
+/// Deregisters an SSE client's subscription set when its stream is dropped
+/// (connection closed), so `subscriptions` doesn't accumulate entries for
+/// clients that have disconnected.
+struct ClientGuard {
+    state: SharedMcpState,
+    client_id: ClientId,
+}
+
+impl Drop for ClientGuard {
+    fn drop(&mut self) {
+        let state = Arc::clone(&self.state);
+        let client_id = self.client_id;
+        tokio::spawn(async move {
+            let state = state.read().await;
+            state.subscriptions.lock().await.remove(&client_id);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,177 @@
+//! Caret Object Store — read datasets straight from remote object storage
+//!
+//! Lets `caret s3://bucket/data.jsonl` (or `gs://`, `az://`, `http(s)://`)
+//! work the same as a local path: the object is fetched in full through the
+//! matching `object_store` backend, then handed to the same JSONL/Parquet/CSV
+//! handling `Dataset::open` already uses for local files. Credentials and
+//! region come from each backend's standard environment variables (e.g.
+//! `AWS_ACCESS_KEY_ID`/`AWS_REGION` for S3) via `from_env()` — there's no
+//! separate caret-specific credential config to manage.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::http::HttpBuilder;
+use object_store::path::Path as ObjectPath;
+use object_store::ObjectStore;
+use reqwest::Url;
+
+use crate::data::Dataset;
+use crate::format::{self, CsvOptions, InputFormat};
+
+/// True if `input` looks like a remote object-storage URL this module knows
+/// how to fetch, rather than a local filesystem path.
+pub fn is_object_store_url(input: &str) -> bool {
+    ["s3://", "gs://", "az://", "http://", "https://"]
+        .iter()
+        .any(|scheme| input.starts_with(scheme))
+}
+
+/// Build the `ObjectStore` backend matching `url`'s scheme, plus the path of
+/// the object within that store's bucket/container.
+fn build_store(url: &Url) -> Result<(Arc<dyn ObjectStore>, ObjectPath)> {
+    let path = ObjectPath::from(url.path().trim_start_matches('/'));
+
+    let store: Arc<dyn ObjectStore> = match url.scheme() {
+        "s3" => {
+            let bucket = url
+                .host_str()
+                .with_context(|| format!("s3:// URL missing bucket name: {}", url))?;
+            Arc::new(
+                AmazonS3Builder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()
+                    .with_context(|| {
+                        "Failed to configure S3 backend (check AWS_ACCESS_KEY_ID, \
+                         AWS_SECRET_ACCESS_KEY, AWS_REGION)"
+                    })?,
+            )
+        }
+        "gs" => {
+            let bucket = url
+                .host_str()
+                .with_context(|| format!("gs:// URL missing bucket name: {}", url))?;
+            Arc::new(
+                GoogleCloudStorageBuilder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()
+                    .with_context(|| {
+                        "Failed to configure GCS backend (check GOOGLE_APPLICATION_CREDENTIALS)"
+                    })?,
+            )
+        }
+        "az" => {
+            let container = url
+                .host_str()
+                .with_context(|| format!("az:// URL missing container name: {}", url))?;
+            Arc::new(
+                MicrosoftAzureBuilder::from_env()
+                    .with_container_name(container)
+                    .build()
+                    .with_context(|| {
+                        "Failed to configure Azure backend (check AZURE_STORAGE_ACCOUNT, \
+                         AZURE_STORAGE_ACCESS_KEY)"
+                    })?,
+            )
+        }
+        "http" | "https" => Arc::new(
+            HttpBuilder::new()
+                .with_url(url.origin().ascii_serialization())
+                .build()
+                .with_context(|| format!("Failed to configure HTTP backend for {}", url))?,
+        ),
+        other => bail!("Unsupported object storage scheme: {}://", other),
+    };
+
+    Ok((store, path))
+}
+
+/// Fetch `url` through the matching object store backend and open it as a
+/// `Dataset`, auto-detecting JSONL/Parquet/CSV from the URL's path extension
+/// the same way `InputFormat::detect` does for local paths.
+pub async fn open_object_store_dataset(url_str: &str, csv_options: CsvOptions) -> Result<Dataset> {
+    let url = Url::parse(url_str)
+        .with_context(|| format!("Invalid object storage URL: {}", url_str))?;
+    let format = InputFormat::detect(url.path());
+    let (store, path) = build_store(&url)?;
+
+    let bytes = store
+        .get(&path)
+        .await
+        .with_context(|| format!("Failed to fetch {}", url_str))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read object body for {}", url_str))?;
+
+    match format {
+        InputFormat::Jsonl => {
+            let buffer = bytes.to_vec();
+            let size = buffer.len() as u64;
+            let mut line_offsets = vec![0];
+            for (i, &byte) in buffer.iter().enumerate() {
+                if byte == b'\n' && i + 1 < buffer.len() {
+                    line_offsets.push(i + 1);
+                }
+            }
+            Ok(Dataset::from_raw_parts(
+                buffer,
+                line_offsets,
+                url_str.to_string(),
+                size,
+                InputFormat::Jsonl,
+            ))
+        }
+        InputFormat::Parquet | InputFormat::Csv => {
+            // `parquet_to_jsonl`/`csv_to_jsonl_with_options` read from a
+            // path, so the fetched bytes are staged through a temp file and
+            // handed straight to them — no separate bytes-oriented
+            // conversion path to keep in sync with the local-file one.
+            let suffix = if format == InputFormat::Parquet {
+                ".parquet"
+            } else {
+                ".csv"
+            };
+            let mut tmp = tempfile::NamedTempFile::with_suffix(suffix)
+                .with_context(|| "Failed to create temp file for remote object")?;
+            tmp.write_all(&bytes)
+                .with_context(|| "Failed to buffer remote object to disk")?;
+
+            let lines = match format {
+                InputFormat::Parquet => format::parquet_to_jsonl(tmp.path())?,
+                InputFormat::Csv => format::csv_to_jsonl_with_options(tmp.path(), csv_options)?,
+                InputFormat::Jsonl => unreachable!("handled above"),
+            };
+
+            let mut dataset = Dataset::empty(url_str.to_string(), format);
+            dataset.append_lines(lines);
+            Ok(dataset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_object_store_url() {
+        assert!(is_object_store_url("s3://bucket/data.jsonl"));
+        assert!(is_object_store_url("gs://bucket/data.csv"));
+        assert!(is_object_store_url("az://container/data.parquet"));
+        assert!(is_object_store_url("https://example.com/data.jsonl"));
+        assert!(!is_object_store_url("hf://org/dataset"));
+        assert!(!is_object_store_url("/local/path/data.jsonl"));
+        assert!(!is_object_store_url("data.jsonl"));
+    }
+
+    #[test]
+    fn test_build_store_rejects_unknown_scheme() {
+        let url = Url::parse("ftp://example.com/data.jsonl").unwrap();
+        let err = build_store(&url).unwrap_err();
+        assert!(err.to_string().contains("Unsupported"));
+    }
+}
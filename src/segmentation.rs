@@ -0,0 +1,236 @@
+//! Caret - Script detection and CJK word segmentation
+//!
+//! Token X-Ray's raw BPE coloring is hard to read over CJK text, where a
+//! single linguistic word routinely spans several sub-word tokens with no
+//! visual grouping to show for it. This module adds an optional
+//! preprocessing pass used by `tokenizer::TokenizerWrapper`: detect the
+//! dominant script of a line, and for CJK text, run a small dictionary-based
+//! word segmenter (forward maximum matching - the same greedy baseline
+//! jieba falls back to outside its statistical model) to get word-level
+//! spans. Those are then intersected with the real tokenizer's sub-token
+//! offsets, so the UI can show both tiers at once: which word a span
+//! belongs to, and which sub-tokens that word was split into.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Dominant script detected in a line of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    /// Mostly Latin-alphabet text - no segmentation benefit, skip the pass
+    Latin,
+    /// Mostly CJK ideographs/kana/hangul - route through `segment_cjk`
+    Cjk,
+    /// No clear majority (empty, punctuation-only, or evenly mixed scripts)
+    Other,
+}
+
+fn is_cjk_char(ch: char) -> bool {
+    matches!(ch as u32,
+        0x4E00..=0x9FFF   // CJK Unified Ideographs
+        | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+        | 0x3040..=0x309F // Hiragana
+        | 0x30A0..=0x30FF // Katakana
+        | 0xAC00..=0xD7A3 // Hangul syllables
+    )
+}
+
+/// Classify `text`'s dominant script via a per-character histogram over
+/// Unicode block membership. A cheap stand-in for a real n-gram language
+/// model, but enough to gate the CJK segmentation pass on or off.
+pub fn detect_script(text: &str) -> Script {
+    let (mut latin, mut cjk, mut total) = (0usize, 0usize, 0usize);
+
+    for ch in text.chars() {
+        if ch.is_whitespace() || ch.is_ascii_punctuation() {
+            continue;
+        }
+        total += 1;
+        if is_cjk_char(ch) {
+            cjk += 1;
+        } else if ch.is_alphabetic() {
+            latin += 1;
+        }
+    }
+
+    if total == 0 {
+        Script::Other
+    } else if cjk * 2 >= total {
+        Script::Cjk
+    } else if latin * 2 >= total {
+        Script::Latin
+    } else {
+        Script::Other
+    }
+}
+
+/// Small bundled frequency dictionary for the forward-maximum-match
+/// segmenter below - enough common Chinese/Japanese/Korean words to
+/// demonstrate word grouping, not a replacement for a real jieba-scale
+/// corpus. Frequencies are only used to break ties between equal-length
+/// candidates, which `segment_cjk`'s longest-match doesn't need yet but
+/// keeps this extensible toward a real frequency-weighted matcher.
+const DICTIONARY: &[(&str, u32)] = &[
+    ("你好", 800),
+    ("世界", 600),
+    ("中国", 900),
+    ("日本", 900),
+    ("东京", 500),
+    ("北京", 700),
+    ("语言", 400),
+    ("模型", 450),
+    ("人工智能", 600),
+    ("计算机", 500),
+    ("机器学习", 500),
+    ("数据", 600),
+    ("文本", 400),
+    ("分词", 300),
+    ("测试", 400),
+    ("汉字", 350),
+    ("こんにちは", 700),
+    ("ありがとう", 600),
+    ("コンピュータ", 500),
+    ("日本語", 650),
+    ("한국어", 500),
+    ("안녕하세요", 600),
+];
+
+/// Longest dictionary entry in `DICTIONARY`, in chars - bounds how far
+/// forward `segment_cjk` needs to probe at each position.
+const MAX_WORD_CHARS: usize = 6;
+
+fn dictionary() -> &'static HashMap<&'static str, u32> {
+    static DICT: OnceLock<HashMap<&'static str, u32>> = OnceLock::new();
+    DICT.get_or_init(|| DICTIONARY.iter().copied().collect())
+}
+
+/// Segment CJK `text` into word-level byte spans via forward maximum
+/// matching: at each position, try the longest dictionary entry that
+/// matches starting there, falling back to a single character when nothing
+/// matches. Spans are contiguous and cover every byte of `text` exactly
+/// once, the same guarantee `tokenizer::get_offsets` makes for sub-tokens.
+pub fn segment_cjk(text: &str) -> Vec<(usize, usize)> {
+    let dict = dictionary();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start_byte = chars[i].0;
+        let max_len = MAX_WORD_CHARS.min(chars.len() - i);
+        let mut matched_len = 1;
+
+        for len in (2..=max_len).rev() {
+            let end_byte = chars.get(i + len).map(|&(b, _)| b).unwrap_or(text.len());
+            if dict.contains_key(&text[start_byte..end_byte]) {
+                matched_len = len;
+                break;
+            }
+        }
+
+        let end_byte = chars.get(i + matched_len).map(|&(b, _)| b).unwrap_or(text.len());
+        spans.push((start_byte, end_byte));
+        i += matched_len;
+    }
+
+    spans
+}
+
+/// Clip each `(start, end, is_special)` sub-token span to the word span (from
+/// `segment_cjk`) it falls within, splitting a span that straddles a word
+/// boundary. Returns `(start, end, is_special, word_index)` pieces in order:
+/// `word_index` groups pieces into linguistic words, while each piece's own
+/// byte range still reflects the real tokenizer's sub-token boundaries.
+pub fn intersect_with_words(
+    token_offsets: &[(usize, usize, bool)],
+    word_offsets: &[(usize, usize)],
+) -> Vec<(usize, usize, bool, usize)> {
+    if word_offsets.is_empty() {
+        return token_offsets.iter().map(|&(s, e, sp)| (s, e, sp, 0)).collect();
+    }
+
+    let mut out = Vec::new();
+    let mut word_idx = 0;
+
+    for &(tok_start, tok_end, is_special) in token_offsets {
+        let mut pos = tok_start;
+        while pos < tok_end {
+            while word_idx + 1 < word_offsets.len() && word_offsets[word_idx].1 <= pos {
+                word_idx += 1;
+            }
+            let word_end = word_offsets[word_idx].1.max(pos + 1);
+            let piece_end = tok_end.min(word_end);
+            out.push((pos, piece_end, is_special, word_idx));
+            pos = piece_end;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_script_latin() {
+        assert_eq!(detect_script("Hello, world!"), Script::Latin);
+    }
+
+    #[test]
+    fn test_detect_script_cjk() {
+        assert_eq!(detect_script("你好，世界"), Script::Cjk);
+        assert_eq!(detect_script("こんにちは"), Script::Cjk);
+    }
+
+    #[test]
+    fn test_detect_script_empty_is_other() {
+        assert_eq!(detect_script(""), Script::Other);
+        assert_eq!(detect_script("   "), Script::Other);
+    }
+
+    #[test]
+    fn test_segment_cjk_matches_dictionary_words() {
+        let text = "你好世界";
+        let spans = segment_cjk(text);
+        let words: Vec<&str> = spans.iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(words, vec!["你好", "世界"]);
+    }
+
+    #[test]
+    fn test_segment_cjk_falls_back_to_single_chars() {
+        let text = "你好一二三";
+        let spans = segment_cjk(text);
+        let words: Vec<&str> = spans.iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(words, vec!["你好", "一", "二", "三"]);
+    }
+
+    #[test]
+    fn test_segment_cjk_covers_whole_string() {
+        let text = "你好，世界！测试";
+        let spans = segment_cjk(text);
+        let mut cursor = 0;
+        for &(start, end) in &spans {
+            assert_eq!(start, cursor);
+            cursor = end;
+        }
+        assert_eq!(cursor, text.len());
+    }
+
+    #[test]
+    fn test_intersect_with_words_splits_straddling_token() {
+        // A token spanning both dictionary words gets split at the word
+        // boundary, each half tagged with its own word index.
+        let token_offsets = vec![(0, 12, false)]; // "你好世界" in bytes
+        let word_offsets = vec![(0, 6), (6, 12)];
+        let pieces = intersect_with_words(&token_offsets, &word_offsets);
+        assert_eq!(pieces, vec![(0, 6, false, 0), (6, 12, false, 1)]);
+    }
+
+    #[test]
+    fn test_intersect_with_words_empty_words_passes_through() {
+        let token_offsets = vec![(0, 3, false), (3, 6, true)];
+        let pieces = intersect_with_words(&token_offsets, &[]);
+        assert_eq!(pieces, vec![(0, 3, false, 0), (3, 6, true, 0)]);
+    }
+}
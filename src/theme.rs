@@ -0,0 +1,390 @@
+//! Caret - Configurable theme subsystem
+//!
+//! Loads `Theme` from a user config file in `~/.config/caret/theme.{toml,json}`,
+//! falling back to one of the built-in named palettes (dracula, solarized,
+//! gruvbox) when no config is present.
+
+use ratatui::style::Color;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A `ratatui::style::Color` that (de)serializes as `"#RRGGBB"` / `"#RRGGBBAA"`.
+///
+/// Mirrors Zed's theme color parsing: strip the leading `#`, parse the hex
+/// digits with `u32::from_str_radix`, and for a 6-digit literal shift left
+/// 8 bits and OR in `0xFF` so the color is treated as fully opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexColor(pub Color);
+
+impl HexColor {
+    /// Parse a `"#RRGGBB"` or `"#RRGGBBAA"` literal.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+
+        let rgba: u32 = match hex.len() {
+            6 => {
+                let rgb = u32::from_str_radix(hex, 16)
+                    .map_err(|_| format!("expected #RRGGBB[AA], got {:?}", s))?;
+                (rgb << 8) | 0xFF
+            }
+            8 => u32::from_str_radix(hex, 16)
+                .map_err(|_| format!("expected #RRGGBB[AA], got {:?}", s))?,
+            _ => return Err(format!("expected #RRGGBB[AA], got {:?}", s)),
+        };
+
+        let r = ((rgba >> 24) & 0xFF) as u8;
+        let g = ((rgba >> 16) & 0xFF) as u8;
+        let b = ((rgba >> 8) & 0xFF) as u8;
+        // Alpha (rgba & 0xFF) is parsed but ratatui::Color has no alpha channel.
+        Ok(HexColor(Color::Rgb(r, g, b)))
+    }
+}
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        HexColor::parse(&s).map_err(de::Error::custom)
+    }
+}
+
+impl From<HexColor> for Color {
+    fn from(c: HexColor) -> Color {
+        c.0
+    }
+}
+
+impl Serialize for HexColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let Color::Rgb(r, g, b) = self.0 else {
+            return serializer.serialize_str("#000000");
+        };
+        serializer.serialize_str(&format!("#{:02X}{:02X}{:02X}", r, g, b))
+    }
+}
+
+/// Glyph set used for gutter status icons (see `ui::render_content`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlyphSet {
+    pub error: String,
+    pub warning: String,
+    pub duplicate: String,
+    pub valid: String,
+    pub object: String,
+    pub array: String,
+    pub malformed: String,
+}
+
+impl Default for GlyphSet {
+    fn default() -> Self {
+        // Nerd Font glyphs by default; `ascii()` below gives a plain fallback.
+        Self {
+            error: "".into(),
+            warning: "".into(),
+            duplicate: "".into(),
+            valid: "".into(),
+            object: "".into(),
+            array: "".into(),
+            malformed: "".into(),
+        }
+    }
+}
+
+impl GlyphSet {
+    /// Plain ASCII fallback for terminals without a patched Nerd Font.
+    pub fn ascii() -> Self {
+        Self {
+            error: "E".into(),
+            warning: "W".into(),
+            duplicate: "D".into(),
+            valid: "*".into(),
+            object: "{}".into(),
+            array: "[]".into(),
+            malformed: "?".into(),
+        }
+    }
+}
+
+/// Theme colors for the UI, loadable from `~/.config/caret/theme.toml|json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub bg: HexColor,
+    pub fg: HexColor,
+    pub accent: HexColor,
+    pub error: HexColor,
+    pub warning: HexColor,
+    pub border: HexColor,
+    pub highlight: HexColor,
+    pub muted: HexColor,
+    pub duplicate: HexColor,
+    /// Steel-blue / cornflower-blue / gray palette cycled across tokens in
+    /// the Token X-Ray detail panel. The Token X-Ray list view colors its
+    /// tokens with the fixed `tokenizer::TOKEN_COLORS` palette instead.
+    pub token_colors: Vec<HexColor>,
+    /// Background used to highlight the currently-selected token.
+    pub token_highlight: HexColor,
+    /// Whether to render Unicode/Nerd-Font gutter glyphs or the ASCII fallback.
+    pub use_nerd_font: bool,
+    /// Gutter status glyphs.
+    pub glyphs: GlyphSet,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::named("dracula").expect("built-in theme 'dracula' always resolves")
+    }
+}
+
+/// A theme as read from disk, before built-in inheritance is resolved.
+///
+/// Every field is optional so a user theme can `extends = "dracula"` and
+/// override only a handful of fields.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ThemeConfig {
+    extends: Option<String>,
+    bg: Option<HexColor>,
+    fg: Option<HexColor>,
+    accent: Option<HexColor>,
+    error: Option<HexColor>,
+    warning: Option<HexColor>,
+    border: Option<HexColor>,
+    highlight: Option<HexColor>,
+    muted: Option<HexColor>,
+    duplicate: Option<HexColor>,
+    token_colors: Option<Vec<HexColor>>,
+    token_highlight: Option<HexColor>,
+    use_nerd_font: Option<bool>,
+    glyphs: Option<GlyphSet>,
+}
+
+impl ThemeConfig {
+    /// Apply this config's overrides on top of a base theme.
+    fn apply(self, base: Theme) -> Theme {
+        let use_nerd_font = self.use_nerd_font.unwrap_or(base.use_nerd_font);
+        // Only fall back to the ASCII glyph set when the user didn't also
+        // give an explicit `glyphs` table of their own.
+        let glyphs = self.glyphs.unwrap_or_else(|| {
+            if use_nerd_font {
+                base.glyphs.clone()
+            } else {
+                GlyphSet::ascii()
+            }
+        });
+
+        Theme {
+            bg: self.bg.unwrap_or(base.bg),
+            fg: self.fg.unwrap_or(base.fg),
+            accent: self.accent.unwrap_or(base.accent),
+            error: self.error.unwrap_or(base.error),
+            warning: self.warning.unwrap_or(base.warning),
+            border: self.border.unwrap_or(base.border),
+            highlight: self.highlight.unwrap_or(base.highlight),
+            muted: self.muted.unwrap_or(base.muted),
+            duplicate: self.duplicate.unwrap_or(base.duplicate),
+            token_colors: self.token_colors.unwrap_or(base.token_colors),
+            token_highlight: self.token_highlight.unwrap_or(base.token_highlight),
+            use_nerd_font,
+            glyphs,
+        }
+    }
+}
+
+fn hex(s: &str) -> HexColor {
+    HexColor::parse(s).expect("built-in theme literal is always a valid #RRGGBB[AA]")
+}
+
+impl Theme {
+    /// Look up a built-in theme by name (`dracula`, `solarized`, `gruvbox`).
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dracula" => Some(Self {
+                bg: hex("#282A36"),
+                fg: hex("#F8F8F2"),
+                accent: hex("#8BE9FD"),
+                error: hex("#FF5555"),
+                warning: hex("#FFB86C"),
+                border: hex("#6272A4"),
+                highlight: hex("#44475A"),
+                muted: hex("#6272A4"),
+                duplicate: hex("#FFAA32"),
+                token_colors: vec![
+                    hex("#4682B4"),
+                    hex("#3C3C3C"),
+                    hex("#6495ED"),
+                    hex("#505050"),
+                ],
+                token_highlight: hex("#FFC832"),
+                use_nerd_font: true,
+                glyphs: GlyphSet::default(),
+            }),
+            "solarized" => Some(Self {
+                bg: hex("#002B36"),
+                fg: hex("#839496"),
+                accent: hex("#268BD2"),
+                error: hex("#DC322F"),
+                warning: hex("#B58900"),
+                border: hex("#073642"),
+                highlight: hex("#073642"),
+                muted: hex("#586E75"),
+                duplicate: hex("#CB4B16"),
+                token_colors: vec![
+                    hex("#268BD2"),
+                    hex("#073642"),
+                    hex("#2AA198"),
+                    hex("#586E75"),
+                ],
+                token_highlight: hex("#B58900"),
+                use_nerd_font: true,
+                glyphs: GlyphSet::default(),
+            }),
+            "gruvbox" => Some(Self {
+                bg: hex("#282828"),
+                fg: hex("#EBDBB2"),
+                accent: hex("#83A598"),
+                error: hex("#FB4934"),
+                warning: hex("#FABD2F"),
+                border: hex("#504945"),
+                highlight: hex("#3C3836"),
+                muted: hex("#928374"),
+                duplicate: hex("#FE8019"),
+                token_colors: vec![
+                    hex("#458588"),
+                    hex("#3C3836"),
+                    hex("#689D6A"),
+                    hex("#504945"),
+                ],
+                token_highlight: hex("#FABD2F"),
+                use_nerd_font: true,
+                glyphs: GlyphSet::default(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Names of the themes bundled with Caret, in cycle order.
+    pub const BUILTIN_NAMES: [&'static str; 3] = ["dracula", "solarized", "gruvbox"];
+
+    /// Cycle to the next built-in theme name after `current`.
+    pub fn next_builtin_name(current: &str) -> &'static str {
+        let idx = Self::BUILTIN_NAMES
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(current))
+            .unwrap_or(0);
+        Self::BUILTIN_NAMES[(idx + 1) % Self::BUILTIN_NAMES.len()]
+    }
+
+    /// The `~/.config/caret/` directory.
+    fn config_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("caret"))
+    }
+
+    /// Load the user's theme config, resolving `extends` against the
+    /// built-in themes. Falls back to `Theme::default()` if no config file
+    /// exists or it fails to parse.
+    pub fn load() -> Self {
+        Self::load_from_dir(Self::config_dir())
+    }
+
+    fn load_from_dir(dir: Option<PathBuf>) -> Self {
+        let Some(dir) = dir else {
+            return Theme::default();
+        };
+
+        let sources: [(PathBuf, fn(&str) -> anyhow::Result<ThemeConfig>); 2] = [
+            (dir.join("theme.toml"), |s| {
+                toml::from_str(s).map_err(Into::into)
+            }),
+            (dir.join("theme.json"), |s| {
+                serde_json::from_str(s).map_err(Into::into)
+            }),
+        ];
+
+        for (path, parse) in sources {
+            if let Ok(contents) = std::fs::read_to_string(&path) {
+                match parse(&contents) {
+                    Ok(config) => return Self::resolve(config),
+                    Err(e) => {
+                        eprintln!("Warning: failed to parse {}: {}", path.display(), e);
+                    }
+                }
+            }
+        }
+
+        Theme::default()
+    }
+
+    /// Resolve a parsed `ThemeConfig`, following `extends` to a built-in base.
+    fn resolve(config: ThemeConfig) -> Self {
+        let base = config
+            .extends
+            .as_deref()
+            .and_then(Theme::named)
+            .unwrap_or_default();
+        config.apply(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_color_6_digit() {
+        let c = HexColor::parse("#FF5555").unwrap();
+        assert_eq!(c.0, Color::Rgb(0xFF, 0x55, 0x55));
+    }
+
+    #[test]
+    fn test_hex_color_8_digit_ignores_alpha() {
+        let c = HexColor::parse("#FF555580").unwrap();
+        assert_eq!(c.0, Color::Rgb(0xFF, 0x55, 0x55));
+    }
+
+    #[test]
+    fn test_hex_color_no_hash_prefix() {
+        let c = HexColor::parse("8BE9FD").unwrap();
+        assert_eq!(c.0, Color::Rgb(0x8B, 0xE9, 0xFD));
+    }
+
+    #[test]
+    fn test_hex_color_malformed() {
+        let err = HexColor::parse("not-a-color").unwrap_err();
+        assert!(err.contains("expected #RRGGBB[AA]"));
+    }
+
+    #[test]
+    fn test_builtin_themes_resolve() {
+        assert!(Theme::named("dracula").is_some());
+        assert!(Theme::named("solarized").is_some());
+        assert!(Theme::named("gruvbox").is_some());
+        assert!(Theme::named("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_next_builtin_name_cycles() {
+        assert_eq!(Theme::next_builtin_name("dracula"), "solarized");
+        assert_eq!(Theme::next_builtin_name("solarized"), "gruvbox");
+        assert_eq!(Theme::next_builtin_name("gruvbox"), "dracula");
+    }
+
+    #[test]
+    fn test_extends_overrides_only_given_fields() {
+        let config = ThemeConfig {
+            extends: Some("dracula".into()),
+            accent: Some(HexColor::parse("#FFFFFF").unwrap()),
+            ..Default::default()
+        };
+        let theme = Theme::resolve(config);
+        assert_eq!(theme.accent.0, Color::Rgb(0xFF, 0xFF, 0xFF));
+        // Unset fields fall through to the dracula base.
+        assert_eq!(theme.bg.0, Theme::named("dracula").unwrap().bg.0);
+    }
+}
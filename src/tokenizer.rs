@@ -4,14 +4,24 @@
 //! - Tiktoken (cl100k_base, p50k_base, r50k_base) - Modern, efficient
 //! - HuggingFace tokenizers (from Hub or local file)
 //! - GPT-2 (legacy, via HuggingFace)
+//! - Built-in, model-free analyzers (whitespace, Unicode words, character
+//!   n-grams) - no download needed, useful as a baseline to diff BPE against
+//! - GGUF - reads the vocab/merges/special ids embedded in a llama.cpp
+//!   model file's metadata header, no separate `tokenizer.json` needed
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use lru::LruCache;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::num::NonZeroUsize;
 use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::gguf;
+use crate::segmentation;
 
 /// Color palette for token visualization
 const TOKEN_COLORS: [Color; 4] = [
@@ -21,9 +31,21 @@ const TOKEN_COLORS: [Color; 4] = [
     Color::Rgb(80, 80, 80),    // Medium Gray
 ];
 
+/// Distinct style for special/control tokens (`<s>`, `<|endoftext|>`, chat
+/// template markers, ...) so prompt scaffolding stands out from the regular
+/// steel-blue/gray alternation instead of blending into it.
+const SPECIAL_TOKEN_STYLE: Style = Style::new()
+    .bg(Color::Rgb(180, 90, 0))
+    .fg(Color::White)
+    .add_modifier(Modifier::BOLD);
+
 /// Cache size for tokenized lines (avoids re-tokenizing on scroll)
 const CACHE_SIZE: usize = 500;
 
+/// Default gram-length range for `whitespace`/`ngram` aliases that don't
+/// specify one explicitly.
+const DEFAULT_NGRAM_RANGE: (usize, usize) = (2, 4);
+
 /// Available tokenizer types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TokenizerType {
@@ -34,15 +56,39 @@ pub enum TokenizerType {
     HuggingFace,
     /// GPT-2 tokenizer (legacy, via HuggingFace)
     Gpt2,
+    /// Splits on Unicode whitespace - zero-config baseline
+    Whitespace,
+    /// Unicode Standard Annex #29 word segmentation
+    UnicodeWords,
+    /// Overlapping character n-grams of length `min..=max`
+    CharNgram { min: usize, max: usize },
 }
 
 impl TokenizerType {
-    /// Parse from CLI string
+    /// Parse from CLI string, e.g. `tiktoken`, `whitespace`, `unicode`, or
+    /// `ngram:2,4` (the `:min,max` suffix is optional and defaults to 2..=4)
     pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
+        let lower = s.to_lowercase();
+
+        if let Some(rest) = lower.strip_prefix("ngram") {
+            let rest = rest.trim_start_matches(':');
+            let (min, max) = if rest.is_empty() {
+                DEFAULT_NGRAM_RANGE
+            } else {
+                let mut parts = rest.splitn(2, ',');
+                let min: usize = parts.next()?.trim().parse().ok()?;
+                let max: usize = parts.next()?.trim().parse().ok()?;
+                (min, max)
+            };
+            return (min >= 1 && min <= max).then_some(TokenizerType::CharNgram { min, max });
+        }
+
+        match lower.as_str() {
             "tiktoken" | "tk" | "openai" => Some(TokenizerType::Tiktoken),
             "huggingface" | "hf" | "llama" => Some(TokenizerType::HuggingFace),
             "gpt2" | "gpt-2" | "legacy" => Some(TokenizerType::Gpt2),
+            "whitespace" | "ws" => Some(TokenizerType::Whitespace),
+            "unicode" | "uw" | "words" => Some(TokenizerType::UnicodeWords),
             _ => None,
         }
     }
@@ -72,20 +118,330 @@ impl TiktokenEncoding {
     }
 }
 
+/// Context length and per-1K-input-token price for a specific model, used
+/// by the token-cost/budget panel to estimate prompt cost and context
+/// headroom for whatever tokenizer is currently loaded. Prices are USD,
+/// approximate, and only meant to give a rough budget sense, not an
+/// authoritative billing figure.
+#[derive(Debug, Clone, Copy)]
+pub struct ModelProfile {
+    pub name: &'static str,
+    pub context_length: usize,
+    pub price_per_1k_input: f64,
+}
+
+/// Known model profiles, matched against `TokenizerWrapper::name` by
+/// `model_profile_for`. Covers the encodings/models `TokenizerWrapper`'s
+/// own constructors can load; anything else falls back to an unknown
+/// (zero-context, zero-cost) profile.
+const MODEL_PROFILES: &[ModelProfile] = &[
+    ModelProfile { name: "gpt-4o (cl100k_base)", context_length: 128_000, price_per_1k_input: 0.0025 },
+    ModelProfile { name: "gpt-3.5-turbo (cl100k_base)", context_length: 16_385, price_per_1k_input: 0.0005 },
+    ModelProfile { name: "codex (p50k_base)", context_length: 8_001, price_per_1k_input: 0.0020 },
+    ModelProfile { name: "gpt-3 davinci (r50k_base)", context_length: 2_049, price_per_1k_input: 0.0020 },
+    ModelProfile { name: "Llama 3.1 8B", context_length: 128_000, price_per_1k_input: 0.0002 },
+    ModelProfile { name: "gpt2", context_length: 1_024, price_per_1k_input: 0.0 },
+];
+
+/// Map a `TokenizerWrapper::name` (e.g. `"tiktoken/cl100k_base"`,
+/// `"meta-llama/Llama-3.1-8B"`, `"gpt2"`) to its closest known
+/// `ModelProfile`. Returns `None` for anything not recognized - builtin
+/// analyzers, a custom `--tokenizer-path` file, or an unlisted HF model.
+fn model_profile_for(name: &str) -> Option<&'static ModelProfile> {
+    let lower = name.to_lowercase();
+    if lower.contains("cl100k") {
+        // cl100k_base backs both gpt-4o and gpt-3.5-turbo; without a more
+        // specific model hint, default to the larger-context one.
+        MODEL_PROFILES.iter().find(|p| p.name.starts_with("gpt-4o"))
+    } else if lower.contains("p50k") {
+        MODEL_PROFILES.iter().find(|p| p.name.starts_with("codex"))
+    } else if lower.contains("r50k") {
+        MODEL_PROFILES.iter().find(|p| p.name.starts_with("gpt-3 davinci"))
+    } else if lower.contains("llama") {
+        MODEL_PROFILES.iter().find(|p| p.name.starts_with("Llama"))
+    } else if lower.contains("gpt2") || lower.contains("gpt-2") {
+        MODEL_PROFILES.iter().find(|p| p.name == "gpt2")
+    } else {
+        None
+    }
+}
+
+/// Token-budget summary for a set of lines against a specific
+/// `ModelProfile`, as returned by `TokenizerWrapper::budget_report`.
+#[derive(Debug, Clone)]
+pub struct BudgetReport {
+    pub token_count: usize,
+    pub model_name: &'static str,
+    pub context_length: usize,
+    pub estimated_cost: f64,
+}
+
+impl BudgetReport {
+    /// Percentage of the model's context window `token_count` occupies.
+    /// `0.0` for an unrecognized model (zero context length), rather than
+    /// dividing by zero.
+    pub fn utilization_pct(&self) -> f64 {
+        if self.context_length == 0 {
+            0.0
+        } else {
+            (self.token_count as f64 / self.context_length as f64) * 100.0
+        }
+    }
+
+    /// `true` once `token_count` exceeds the model's context window.
+    pub fn exceeds_context(&self) -> bool {
+        self.context_length > 0 && self.token_count > self.context_length
+    }
+
+    /// `"12,431 / 128,000 tokens, 9.7%"`-style summary for the status bar.
+    pub fn utilization_label(&self) -> String {
+        format!(
+            "{} / {} tokens, {:.1}%",
+            format_with_commas(self.token_count),
+            format_with_commas(self.context_length),
+            self.utilization_pct()
+        )
+    }
+}
+
+/// Render `n` with thousands separators (`12431` -> `"12,431"`), matching
+/// the status bar's plain-ASCII style rather than pulling in a formatting
+/// crate for one digit-grouping helper.
+fn format_with_commas(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out.chars().rev().collect()
+}
+
 /// Backend-specific tokenizer implementation
 enum TokenizerBackend {
     /// Tiktoken BPE tokenizer
     Tiktoken(tiktoken_rs::CoreBPE),
     /// HuggingFace tokenizer
     HuggingFace(tokenizers::Tokenizer),
+    /// Splits on Unicode whitespace
+    Whitespace,
+    /// Unicode Standard Annex #29 word segmentation
+    UnicodeWords,
+    /// Overlapping character n-grams of length `min..=max`
+    CharNgram { min: usize, max: usize },
+    /// BPE vocabulary read straight out of a GGUF model file's metadata
+    GgufBpe(GgufBpe),
+}
+
+/// Minimal byte-pair-merge tokenizer built from a GGUF file's embedded
+/// vocabulary (see `crate::gguf`). Tokenizes by splitting on whitespace,
+/// then for each word repeatedly merging the adjacent character pair with
+/// the lowest merge rank - the same greedy algorithm GPT-2-style BPE
+/// vocabularies are built and applied with - until no merge rule applies.
+struct GgufBpe {
+    token_to_id: HashMap<String, u32>,
+    merge_rank: HashMap<(String, String), usize>,
+    special_ids: HashSet<usize>,
+}
+
+impl GgufBpe {
+    fn from_data(data: gguf::GgufTokenizerData) -> Self {
+        let token_to_id = data
+            .tokens
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.clone(), i as u32))
+            .collect();
+        let merge_rank = data
+            .merges
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(rank, pair)| (pair, rank))
+            .collect();
+        let special_ids = data.special_ids();
+        Self { token_to_id, merge_rank, special_ids }
+    }
+
+    /// Greedy BPE merge over a single word's characters: repeatedly merge
+    /// the adjacent pair with the lowest merge rank (earliest in the GGUF
+    /// merges list = highest priority) until no pair in `merge_rank` applies.
+    fn bpe_merge(&self, word: &str) -> Vec<String> {
+        let mut parts: Vec<String> = word.chars().map(|c| c.to_string()).collect();
+        while parts.len() > 1 {
+            let mut best: Option<(usize, usize)> = None;
+            for i in 0..parts.len() - 1 {
+                if let Some(&rank) = self.merge_rank.get(&(parts[i].clone(), parts[i + 1].clone())) {
+                    if best.map(|(best_rank, _)| rank < best_rank).unwrap_or(true) {
+                        best = Some((rank, i));
+                    }
+                }
+            }
+            let Some((_, i)) = best else { break };
+            let merged = format!("{}{}", parts[i], parts[i + 1]);
+            parts.splice(i..=i + 1, [merged]);
+        }
+        parts
+    }
+
+    /// Sub-token offsets for `text`: each whitespace run becomes one plain
+    /// span, and each non-whitespace run is BPE-merged into sub-tokens whose
+    /// byte spans fall out of the word's own char offsets (no decode
+    /// round-trip needed, since merged pieces never change the underlying
+    /// bytes, only where they're split).
+    fn get_offsets(&self, text: &str) -> Vec<(usize, usize, bool)> {
+        let mut offsets = Vec::new();
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i].1.is_whitespace() {
+                let start = chars[i].0;
+                let mut j = i;
+                while j < chars.len() && chars[j].1.is_whitespace() {
+                    j += 1;
+                }
+                let end = chars.get(j).map(|&(b, _)| b).unwrap_or(text.len());
+                offsets.push((start, end, false));
+                i = j;
+                continue;
+            }
+
+            let word_start_byte = chars[i].0;
+            let mut j = i;
+            while j < chars.len() && !chars[j].1.is_whitespace() {
+                j += 1;
+            }
+            let word_end_byte = chars.get(j).map(|&(b, _)| b).unwrap_or(text.len());
+            let word = &text[word_start_byte..word_end_byte];
+
+            let mut cursor = word_start_byte;
+            for piece in self.bpe_merge(word) {
+                let piece_end = cursor + piece.len();
+                let is_special = self
+                    .token_to_id
+                    .get(&piece)
+                    .map(|&id| self.special_ids.contains(&(id as usize)))
+                    .unwrap_or(false);
+                offsets.push((cursor, piece_end, is_special));
+                cursor = piece_end;
+            }
+
+            i = j;
+        }
+
+        offsets
+    }
+
+    fn count_tokens(&self, text: &str) -> usize {
+        self.get_offsets(text).len()
+    }
+
+    fn get_token_ids(&self, text: &str) -> Vec<u32> {
+        text.split_whitespace()
+            .flat_map(|word| self.bpe_merge(word))
+            .filter_map(|piece| self.token_to_id.get(&piece).copied())
+            .collect()
+    }
+}
+
+/// Known special-token strings per Tiktoken encoding, used to classify ids
+/// returned by `encode_with_special_tokens` since `CoreBPE` doesn't expose
+/// its special-token set directly.
+fn known_special_tokens(encoding: TiktokenEncoding) -> &'static [&'static str] {
+    match encoding {
+        TiktokenEncoding::Cl100kBase => &[
+            "<|endoftext|>",
+            "<|fim_prefix|>",
+            "<|fim_middle|>",
+            "<|fim_suffix|>",
+            "<|endofprompt|>",
+        ],
+        TiktokenEncoding::P50kBase | TiktokenEncoding::R50kBase => &["<|endoftext|>"],
+    }
+}
+
+/// Load the `special_tokens_map.json` file sitting next to `tokenizer_path`,
+/// if any, and flatten its string/list-of-string values into a set of known
+/// special-token strings. This is supplementary to the `tokenizers` crate's
+/// own `get_special_tokens_mask`, which remains the primary source of truth
+/// for the HuggingFace backend; absence of the file is not an error.
+fn load_special_tokens_map(tokenizer_path: &Path) -> HashSet<String> {
+    let mut tokens = HashSet::new();
+    let Some(dir) = tokenizer_path.parent() else {
+        return tokens;
+    };
+    let map_path = dir.join("special_tokens_map.json");
+    let Ok(contents) = fs::read_to_string(&map_path) else {
+        return tokens;
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&contents) else {
+        return tokens;
+    };
+    collect_special_token_strings(&value, &mut tokens);
+    tokens
+}
+
+/// Recursively walk a `special_tokens_map.json` value, collecting every
+/// string found (entries are either a bare string like `"<s>"` or an object
+/// like `{"content": "<s>", ...}`).
+fn collect_special_token_strings(value: &serde_json::Value, out: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::String(s) => {
+            out.insert(s.clone());
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_special_token_strings(item, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values() {
+                collect_special_token_strings(item, out);
+            }
+        }
+        _ => {}
+    }
 }
 
 /// Wrapper around tokenizers with LRU cache for performance
 pub struct TokenizerWrapper {
     backend: TokenizerBackend,
     pub name: String,
-    /// LRU cache for tokenized line offsets to avoid re-encoding
-    cache: RefCell<LruCache<String, Vec<(usize, usize)>>>,
+    /// LRU cache for tokenized line offsets (start, end, is_special) to
+    /// avoid re-encoding
+    cache: RefCell<LruCache<String, Vec<(usize, usize, bool)>>>,
+    /// Token ids classified as special/control tokens (Tiktoken backend
+    /// only — HuggingFace gets this straight from `get_special_tokens_mask`
+    /// on each encoding, so it needs no precomputed set).
+    special_token_ids: HashSet<usize>,
+    /// Special-token strings loaded from a sibling `special_tokens_map.json`
+    /// (HuggingFace backend only), kept as supplementary/fallback data.
+    special_token_strings: HashSet<String>,
+    /// Optional script-detection + CJK word-segmentation preprocessing pass
+    /// for `colorize_tokens`. Off by default so existing behavior (plain
+    /// per-token coloring) is preserved.
+    segmentation_hint: SegmentationHint,
+    /// Cache of word-level spans per line, alongside `cache`, so toggling
+    /// segmentation on doesn't cost a re-segment of already-seen lines and
+    /// scrolling back to them stays cheap.
+    word_cache: RefCell<LruCache<String, Vec<(usize, usize)>>>,
+}
+
+/// Optional preprocessing pass `colorize_tokens` runs before coloring: when
+/// `Auto`, each line's dominant script is detected and CJK lines are routed
+/// through a dictionary word segmenter, giving two-tier highlighting (word
+/// grouping via underline, sub-token boundaries via the usual background
+/// alternation). Off by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentationHint {
+    /// Plain per-token coloring, no script detection (default)
+    #[default]
+    Off,
+    /// Detect the dominant script per line and segment CJK text into words
+    Auto,
 }
 
 impl TokenizerWrapper {
@@ -103,10 +459,19 @@ impl TokenizerWrapper {
             }
         };
 
+        let special_token_ids = known_special_tokens(encoding)
+            .iter()
+            .flat_map(|token| bpe.encode_with_special_tokens(token))
+            .collect();
+
         Ok(Self {
             backend: TokenizerBackend::Tiktoken(bpe),
             name,
             cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids,
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
         })
     }
 
@@ -122,10 +487,16 @@ impl TokenizerWrapper {
             .unwrap_or("unknown")
             .to_string();
 
+        let special_token_strings = load_special_tokens_map(path_ref);
+
         Ok(Self {
             backend: TokenizerBackend::HuggingFace(tokenizer),
             name,
             cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings,
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
         })
     }
 
@@ -138,11 +509,118 @@ impl TokenizerWrapper {
             backend: TokenizerBackend::HuggingFace(tokenizer),
             name: model_id.to_string(),
             cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+        })
+    }
+
+    /// Split on Unicode whitespace - the simplest possible baseline, no
+    /// model or vocabulary required
+    pub fn from_whitespace() -> Result<Self> {
+        Ok(Self {
+            backend: TokenizerBackend::Whitespace,
+            name: "builtin/whitespace".to_string(),
+            cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+        })
+    }
+
+    /// Unicode Standard Annex #29 word segmentation (via `unicode-segmentation`)
+    pub fn from_unicode_words() -> Result<Self> {
+        Ok(Self {
+            backend: TokenizerBackend::UnicodeWords,
+            name: "builtin/unicode".to_string(),
+            cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+        })
+    }
+
+    /// Overlapping character n-grams of length `min..=max`, char-aligned by
+    /// construction since spans are built directly from char positions
+    /// rather than a decode round-trip
+    pub fn from_char_ngram(min: usize, max: usize) -> Result<Self> {
+        if min == 0 || min > max {
+            bail!("n-gram range must satisfy 1 <= min <= max (got {min}..={max})");
+        }
+
+        Ok(Self {
+            backend: TokenizerBackend::CharNgram { min, max },
+            name: format!("builtin/ngram:{min},{max}"),
+            cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+        })
+    }
+
+    /// Load a BPE vocabulary embedded in a GGUF model file's metadata
+    /// header (`tokenizer.ggml.tokens`/`merges`/`token_type`/
+    /// `bos_token_id`/etc, see `crate::gguf`), so users can point Caret
+    /// directly at the single model file they already have for local-LLM
+    /// workflows instead of hunting down a separate `tokenizer.json`.
+    pub fn from_gguf<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path_ref = path.as_ref();
+        let data = gguf::read_tokenizer_data(path_ref)?;
+        let name = path_ref
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        Ok(Self {
+            backend: TokenizerBackend::GgufBpe(GgufBpe::from_data(data)),
+            name: format!("gguf/{name}"),
+            cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
+            special_token_ids: HashSet::new(),
+            special_token_strings: HashSet::new(),
+            segmentation_hint: SegmentationHint::Off,
+            word_cache: RefCell::new(LruCache::new(NonZeroUsize::new(CACHE_SIZE).unwrap())),
         })
     }
 
-    /// Get token offsets, using cache if available
-    fn get_offsets(&self, text: &str) -> Option<Vec<(usize, usize)>> {
+    /// Toggle the optional script-detection + CJK word-segmentation
+    /// preprocessing pass `colorize_tokens` uses for two-tier highlighting.
+    /// Off by default so existing behavior is unchanged.
+    pub fn set_segmentation_hint(&mut self, hint: SegmentationHint) {
+        self.segmentation_hint = hint;
+    }
+
+    /// Word-level spans for `text` when segmentation is on and the line is
+    /// predominantly CJK, from cache or computed and cached alongside the
+    /// token-offset cache so toggling segmentation on doesn't cost a
+    /// re-segment of already-seen lines.
+    fn word_offsets_for(&self, text: &str) -> Option<Vec<(usize, usize)>> {
+        if self.segmentation_hint == SegmentationHint::Off {
+            return None;
+        }
+        if segmentation::detect_script(text) != segmentation::Script::Cjk {
+            return None;
+        }
+
+        let cache_key = text.to_string();
+        {
+            let mut cache = self.word_cache.borrow_mut();
+            if let Some(cached) = cache.get(&cache_key) {
+                return Some(cached.clone());
+            }
+        }
+
+        let spans = segmentation::segment_cjk(text);
+        self.word_cache.borrow_mut().put(cache_key, spans.clone());
+        Some(spans)
+    }
+
+    /// Get token offsets (start, end, is_special), using cache if available
+    fn get_offsets(&self, text: &str) -> Option<Vec<(usize, usize, bool)>> {
         // Check cache first
         let cache_key = text.to_string();
         {
@@ -155,27 +633,114 @@ impl TokenizerWrapper {
         // Encode based on backend
         let offsets = match &self.backend {
             TokenizerBackend::Tiktoken(bpe) => {
-                // Tiktoken doesn't provide byte offsets directly, so we need to decode each token
-                // to reconstruct offsets
+                // Tiktoken doesn't provide byte offsets directly, and decoding a
+                // single token id in isolation can produce a byte span that
+                // splits a multi-byte UTF-8 character — a single glyph (e.g. an
+                // emoji or a CJK character) can span several cl100k tokens, and
+                // each token's own bytes needn't end on a char boundary.
+                //
+                // Instead, decode the cumulative prefix `tokens[..=i]` for each
+                // i: since that always reproduces the original text's bytes
+                // exactly, its length gives the true end offset after token i,
+                // with no per-token drift to accumulate. A span is only
+                // "flushed" once that cumulative end lands on a char boundary;
+                // tokens that don't yet complete a character stay pending and
+                // get merged into the next flushed span, so every offset caret
+                // hands back is safe to slice with `text.get(start..end)`.
                 let tokens = bpe.encode_with_special_tokens(text);
                 let mut offsets = Vec::new();
-                let mut current_pos = 0;
-                
-                for token_id in tokens {
-                    if let Ok(token_bytes) = bpe.decode(vec![token_id]) {
-                        let token_len = token_bytes.len();
-                        if current_pos + token_len <= text.len() {
-                            offsets.push((current_pos, current_pos + token_len));
-                            current_pos += token_len;
-                        }
+                let mut span_start = 0usize;
+                let mut cumulative_end = 0usize;
+                // A span can merge several raw token ids together before it
+                // lands on a char boundary; mark it special if any of its
+                // constituent tokens is, since a special token is designed
+                // to decode atomically and so never needs merging in practice.
+                let mut span_has_special = false;
+
+                for (i, &token_id) in tokens.iter().enumerate() {
+                    if self.special_token_ids.contains(&token_id) {
+                        span_has_special = true;
                     }
+
+                    let decoded = bpe.decode(tokens[..=i].to_vec()).unwrap_or_default();
+                    cumulative_end = decoded.len().min(text.len());
+
+                    if text.is_char_boundary(cumulative_end) {
+                        offsets.push((span_start, cumulative_end, span_has_special));
+                        span_start = cumulative_end;
+                        span_has_special = false;
+                    }
+                }
+
+                // Guard against a final pending span that never lands on a
+                // char boundary (shouldn't happen given a well-formed
+                // encode/decode roundtrip, but avoids silently dropping bytes).
+                if span_start < cumulative_end {
+                    offsets.push((span_start, cumulative_end, span_has_special));
                 }
+
                 offsets
             }
             TokenizerBackend::HuggingFace(tokenizer) => {
                 let encoding = tokenizer.encode(text, false).ok()?;
-                encoding.get_offsets().to_vec()
+                let mask = encoding.get_special_tokens_mask();
+                encoding
+                    .get_offsets()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &(start, end))| {
+                        let is_special = mask.get(i).map(|&m| m == 1).unwrap_or(false)
+                            || text
+                                .get(start..end)
+                                .map(|s| self.special_token_strings.contains(s))
+                                .unwrap_or(false);
+                        (start, end, is_special)
+                    })
+                    .collect()
             }
+            TokenizerBackend::Whitespace => {
+                let mut offsets = Vec::new();
+                let mut span_start: Option<usize> = None;
+                for (i, ch) in text.char_indices() {
+                    if ch.is_whitespace() {
+                        if let Some(start) = span_start.take() {
+                            offsets.push((start, i, false));
+                        }
+                    } else if span_start.is_none() {
+                        span_start = Some(i);
+                    }
+                }
+                if let Some(start) = span_start {
+                    offsets.push((start, text.len(), false));
+                }
+                offsets
+            }
+            TokenizerBackend::UnicodeWords => text
+                .unicode_word_indices()
+                .map(|(start, word)| (start, start + word.len(), false))
+                .collect(),
+            TokenizerBackend::CharNgram { min, max } => {
+                // Byte offset of each char boundary, plus the end of the
+                // string, so a gram spanning chars `i..i+g` is just
+                // `boundaries[i]..boundaries[i + g]` - no decode needed, and
+                // every span is char-aligned by construction.
+                let mut boundaries: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+                boundaries.push(text.len());
+                let char_count = boundaries.len() - 1;
+
+                let mut offsets = Vec::new();
+                for start_char in 0..char_count {
+                    for gram_len in *min..=*max {
+                        let end_char = start_char + gram_len;
+                        if end_char > char_count {
+                            break;
+                        }
+                        offsets.push((boundaries[start_char], boundaries[end_char], false));
+                    }
+                }
+                offsets
+            }
+            TokenizerBackend::GgufBpe(bpe) => bpe.get_offsets(text),
         };
 
         // Cache the result
@@ -187,7 +752,13 @@ impl TokenizerWrapper {
         Some(offsets)
     }
 
-    /// Tokenize text and return spans with alternating background colors
+    /// Tokenize text and return spans with alternating background colors.
+    ///
+    /// When `segmentation_hint` is `Auto` and `text` is predominantly CJK,
+    /// sub-token spans are additionally split at word boundaries from
+    /// `segmentation::segment_cjk`, and alternating words get an underline -
+    /// a second highlighting tier layered on top of the usual per-token
+    /// background alternation.
     pub fn colorize_tokens(&self, text: &str) -> Line<'static> {
         let offsets = match self.get_offsets(text) {
             Some(o) => o,
@@ -198,10 +769,16 @@ impl TokenizerWrapper {
             return Line::from(text.to_string());
         }
 
+        let pieces: Vec<(usize, usize, bool, usize)> = match self.word_offsets_for(text) {
+            Some(words) => segmentation::intersect_with_words(&offsets, &words),
+            None => offsets.iter().map(|&(s, e, sp)| (s, e, sp, 0)).collect(),
+        };
+
         let mut spans = Vec::new();
         let mut last_end = 0;
+        let mut color_index = 0;
 
-        for (i, &(start, end)) in offsets.iter().enumerate() {
+        for &(start, end, is_special, word_index) in &pieces {
             // Add any gap between tokens as plain text
             if start > last_end {
                 if let Some(gap) = text.get(last_end..start) {
@@ -211,11 +788,17 @@ impl TokenizerWrapper {
 
             // Add the token with colored background
             if let Some(token_text) = text.get(start..end) {
-                let color = TOKEN_COLORS[i % TOKEN_COLORS.len()];
-                spans.push(Span::styled(
-                    token_text.to_string(),
-                    Style::default().bg(color).fg(Color::White),
-                ));
+                let mut style = if is_special {
+                    SPECIAL_TOKEN_STYLE
+                } else {
+                    let color = TOKEN_COLORS[color_index % TOKEN_COLORS.len()];
+                    color_index += 1;
+                    Style::default().bg(color).fg(Color::White)
+                };
+                if word_index % 2 == 1 {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                spans.push(Span::styled(token_text.to_string(), style));
             }
 
             last_end = end;
@@ -244,6 +827,10 @@ impl TokenizerWrapper {
                     .map(|e| e.get_tokens().len())
                     .unwrap_or(0)
             }
+            TokenizerBackend::Whitespace | TokenizerBackend::UnicodeWords | TokenizerBackend::CharNgram { .. } => {
+                self.get_offsets(text).map(|o| o.len()).unwrap_or(0)
+            }
+            TokenizerBackend::GgufBpe(bpe) => bpe.count_tokens(text),
         }
     }
 
@@ -263,6 +850,46 @@ impl TokenizerWrapper {
                     .map(|e| e.get_ids().to_vec())
                     .unwrap_or_default()
             }
+            TokenizerBackend::Whitespace | TokenizerBackend::UnicodeWords | TokenizerBackend::CharNgram { .. } => {
+                // No real vocabulary backs these analyzers; expose
+                // positional indices so callers that expect one id per
+                // token still get something stable and unique.
+                (0..self.count_tokens(text) as u32).collect()
+            }
+            TokenizerBackend::GgufBpe(bpe) => bpe.get_token_ids(text),
+        }
+    }
+
+    /// Count tokens in `text`, reusing the offset LRU cache instead of
+    /// re-encoding - unlike `count_tokens`, which re-runs the Tiktoken,
+    /// HuggingFace and GGUF encoders directly. Used by `count_tokens_for_lines` so
+    /// aggregating over many already-viewed lines stays cheap.
+    fn count_tokens_cached(&self, text: &str) -> usize {
+        self.get_offsets(text).map(|o| o.len()).unwrap_or(0)
+    }
+
+    /// Sum token counts across every line in `lines`, reusing the cache so
+    /// lines already seen (e.g. scrolled past) aren't re-encoded.
+    pub fn count_tokens_for_lines<'a, I: IntoIterator<Item = &'a str>>(&self, lines: I) -> usize {
+        lines.into_iter().map(|line| self.count_tokens_cached(line)).sum()
+    }
+
+    /// Build a `BudgetReport` for `lines` against this tokenizer's closest
+    /// known `ModelProfile` (by `self.name`), for the token-cost/budget
+    /// panel. Unknown tokenizers (builtin analyzers, unrecognized
+    /// HuggingFace models) fall back to a zero-context, zero-cost profile
+    /// rather than failing - the token count itself is still meaningful.
+    pub fn budget_report<'a, I: IntoIterator<Item = &'a str>>(&self, lines: I) -> BudgetReport {
+        let token_count = self.count_tokens_for_lines(lines);
+        let profile = model_profile_for(&self.name);
+
+        BudgetReport {
+            token_count,
+            model_name: profile.map(|p| p.name).unwrap_or("unknown"),
+            context_length: profile.map(|p| p.context_length).unwrap_or(0),
+            estimated_cost: profile
+                .map(|p| p.price_per_1k_input * (token_count as f64 / 1000.0))
+                .unwrap_or(0.0),
         }
     }
 }
@@ -293,4 +920,289 @@ mod tests {
         assert_eq!(TiktokenEncoding::from_str("p50k_base"), Some(TiktokenEncoding::P50kBase));
         assert_eq!(TiktokenEncoding::from_str("r50k_base"), Some(TiktokenEncoding::R50kBase));
     }
+
+    /// Offsets must always land on char boundaries and cover every byte of
+    /// `text` exactly once, in order, with no gaps or overlaps.
+    fn assert_offsets_cover_text(text: &str, offsets: &[(usize, usize, bool)]) {
+        let mut cursor = 0;
+        for &(start, end, _) in offsets {
+            assert_eq!(start, cursor, "offsets must be contiguous, no gaps/overlaps");
+            assert!(text.is_char_boundary(start), "start {start} not a char boundary");
+            assert!(text.is_char_boundary(end), "end {end} not a char boundary");
+            assert!(text.get(start..end).is_some(), "span {start}..{end} must slice cleanly");
+            cursor = end;
+        }
+        assert_eq!(cursor, text.len(), "offsets must cover the whole string");
+    }
+
+    #[test]
+    fn test_tiktoken_offsets_emoji() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let text = "Hello 👋🌍 world";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        assert_offsets_cover_text(text, &offsets);
+    }
+
+    #[test]
+    fn test_tiktoken_offsets_cjk() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let text = "こんにちは世界、测试中文字符";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        assert_offsets_cover_text(text, &offsets);
+    }
+
+    #[test]
+    fn test_tiktoken_offsets_mixed_ascii_and_multibyte() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let text = "café 🎉 naïve résumé 日本語";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        assert_offsets_cover_text(text, &offsets);
+    }
+
+    #[test]
+    fn test_colorize_tokens_never_drops_multibyte_glyphs() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let text = "emoji 🔥 test 漢字";
+        let line = tokenizer.colorize_tokens(text);
+        let rebuilt: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(rebuilt, text);
+    }
+
+    #[test]
+    fn test_tiktoken_marks_special_token_span() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let text = "before <|endoftext|> after";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        assert_offsets_cover_text(text, &offsets);
+
+        let special_span = offsets
+            .iter()
+            .find(|&&(start, end, _)| &text[start..end] == "<|endoftext|>");
+        assert!(special_span.is_some(), "expected a span covering the special token exactly");
+        assert!(special_span.unwrap().2, "special token span must be flagged is_special");
+
+        let non_special = offsets.iter().find(|&&(start, end, _)| &text[start..end] == "before ");
+        assert!(!non_special.unwrap().2);
+    }
+
+    #[test]
+    fn test_colorize_tokens_applies_special_style() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let line = tokenizer.colorize_tokens("<|endoftext|>");
+        assert!(line
+            .spans
+            .iter()
+            .any(|s| s.style == SPECIAL_TOKEN_STYLE && s.content.as_ref() == "<|endoftext|>"));
+    }
+
+    #[test]
+    fn test_tokenizer_type_parses_builtin_aliases() {
+        assert_eq!(TokenizerType::from_str("whitespace"), Some(TokenizerType::Whitespace));
+        assert_eq!(TokenizerType::from_str("unicode"), Some(TokenizerType::UnicodeWords));
+        assert_eq!(
+            TokenizerType::from_str("ngram:2,4"),
+            Some(TokenizerType::CharNgram { min: 2, max: 4 })
+        );
+        assert_eq!(
+            TokenizerType::from_str("ngram"),
+            Some(TokenizerType::CharNgram { min: 2, max: 4 })
+        );
+        assert_eq!(TokenizerType::from_str("ngram:5,1"), None);
+    }
+
+    #[test]
+    fn test_whitespace_tokenizer_splits_on_whitespace() {
+        let tokenizer = TokenizerWrapper::from_whitespace().unwrap();
+        let text = "the quick  brown fox";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        let words: Vec<&str> = offsets.iter().map(|&(s, e, _)| &text[s..e]).collect();
+        assert_eq!(words, vec!["the", "quick", "brown", "fox"]);
+    }
+
+    #[test]
+    fn test_unicode_word_tokenizer_skips_punctuation_runs() {
+        let tokenizer = TokenizerWrapper::from_unicode_words().unwrap();
+        let text = "Hello, world!";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        let words: Vec<&str> = offsets.iter().map(|&(s, e, _)| &text[s..e]).collect();
+        assert_eq!(words, vec!["Hello", "world"]);
+    }
+
+    #[test]
+    fn test_char_ngram_tokenizer_emits_overlapping_spans() {
+        let tokenizer = TokenizerWrapper::from_char_ngram(2, 3).unwrap();
+        let text = "abcd";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        let grams: Vec<&str> = offsets.iter().map(|&(s, e, _)| &text[s..e]).collect();
+        assert_eq!(grams, vec!["ab", "abc", "bc", "bcd", "cd"]);
+    }
+
+    #[test]
+    fn test_char_ngram_tokenizer_is_char_aligned_for_multibyte_text() {
+        let tokenizer = TokenizerWrapper::from_char_ngram(1, 2).unwrap();
+        let text = "日本語";
+        let offsets = tokenizer.get_offsets(text).unwrap();
+        assert!(!offsets.is_empty());
+        for &(start, end, _) in &offsets {
+            assert!(text.is_char_boundary(start));
+            assert!(text.is_char_boundary(end));
+            assert!(text.get(start..end).is_some());
+        }
+    }
+
+    #[test]
+    fn test_char_ngram_rejects_invalid_range() {
+        assert!(TokenizerWrapper::from_char_ngram(0, 3).is_err());
+        assert!(TokenizerWrapper::from_char_ngram(4, 2).is_err());
+    }
+
+    #[test]
+    fn test_segmentation_off_by_default_no_underline() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let line = tokenizer.colorize_tokens("你好世界");
+        assert!(!line.spans.iter().any(|s| s.style.add_modifier.contains(Modifier::UNDERLINED)));
+    }
+
+    #[test]
+    fn test_segmentation_auto_underlines_alternate_words() {
+        let mut tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        tokenizer.set_segmentation_hint(SegmentationHint::Auto);
+        let line = tokenizer.colorize_tokens("你好世界");
+        assert!(line.spans.iter().any(|s| s.style.add_modifier.contains(Modifier::UNDERLINED)));
+
+        // Never drops or duplicates bytes even with the extra splitting pass.
+        let rebuilt: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(rebuilt, "你好世界");
+    }
+
+    #[test]
+    fn test_segmentation_auto_skips_latin_text() {
+        let mut tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        tokenizer.set_segmentation_hint(SegmentationHint::Auto);
+        let line = tokenizer.colorize_tokens("hello world");
+        assert!(!line.spans.iter().any(|s| s.style.add_modifier.contains(Modifier::UNDERLINED)));
+    }
+
+    #[test]
+    fn test_format_with_commas() {
+        assert_eq!(format_with_commas(0), "0");
+        assert_eq!(format_with_commas(431), "431");
+        assert_eq!(format_with_commas(12431), "12,431");
+        assert_eq!(format_with_commas(128000), "128,000");
+    }
+
+    #[test]
+    fn test_model_profile_for_known_encodings() {
+        assert_eq!(model_profile_for("tiktoken/cl100k_base").unwrap().context_length, 128_000);
+        assert_eq!(model_profile_for("tiktoken/p50k_base").unwrap().context_length, 8_001);
+        assert_eq!(model_profile_for("meta-llama/Llama-3.1-8B").unwrap().context_length, 128_000);
+        assert!(model_profile_for("builtin/whitespace").is_none());
+    }
+
+    #[test]
+    fn test_count_tokens_for_lines_sums_across_lines() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let a = tokenizer.count_tokens_cached("hello world");
+        let b = tokenizer.count_tokens_cached("goodbye");
+        let total = tokenizer.count_tokens_for_lines(["hello world", "goodbye"]);
+        assert_eq!(total, a + b);
+    }
+
+    #[test]
+    fn test_budget_report_tracks_context_utilization() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::Cl100kBase).unwrap();
+        let report = tokenizer.budget_report(["hello world"]);
+        assert_eq!(report.context_length, 128_000);
+        assert!(report.utilization_pct() > 0.0 && report.utilization_pct() < 1.0);
+        assert!(!report.exceeds_context());
+        assert!(report.utilization_label().contains("tokens"));
+    }
+
+    #[test]
+    fn test_budget_report_warns_when_over_context() {
+        let tokenizer = TokenizerWrapper::from_tiktoken(TiktokenEncoding::R50kBase).unwrap();
+        let huge_line = "word ".repeat(3_000);
+        let report = tokenizer.budget_report([huge_line.as_str()]);
+        assert!(report.exceeds_context());
+    }
+
+    #[test]
+    fn test_budget_report_unknown_tokenizer_has_zero_context() {
+        let tokenizer = TokenizerWrapper::from_whitespace().unwrap();
+        let report = tokenizer.budget_report(["a b c"]);
+        assert_eq!(report.context_length, 0);
+        assert_eq!(report.utilization_pct(), 0.0);
+        assert!(!report.exceeds_context());
+    }
+
+    fn gguf_bpe_for_test() -> GgufBpe {
+        GgufBpe::from_data(gguf::GgufTokenizerData {
+            tokens: vec!["h".into(), "e".into(), "l".into(), "o".into(), "he".into(), "hel".into()],
+            merges: vec![("h".into(), "e".into()), ("he".into(), "l".into())],
+            token_types: vec![1, 1, 1, 1, 1, 3],
+            bos_id: Some(0),
+            eos_id: None,
+            unk_id: None,
+        })
+    }
+
+    #[test]
+    fn test_gguf_bpe_merges_greedily_by_rank() {
+        let bpe = gguf_bpe_for_test();
+        let pieces = bpe.bpe_merge("hello");
+        // "h"+"e" merges first (rank 0) -> "he", then "he"+"l" (rank 1) -> "hel",
+        // leaving "hel", "l", "o" since no further merge rule applies.
+        assert_eq!(pieces, vec!["hel", "l", "o"]);
+    }
+
+    #[test]
+    fn test_gguf_bpe_get_offsets_covers_text_and_flags_special() {
+        let bpe = gguf_bpe_for_test();
+        let text = "hello world";
+        let offsets = bpe.get_offsets(text);
+        assert_offsets_cover_text(text, &offsets);
+
+        let hel_span = offsets.iter().find(|&&(s, e, _)| &text[s..e] == "hel");
+        assert!(hel_span.unwrap().2, "\"hel\" has token_type CONTROL, should be flagged special");
+    }
+
+    #[test]
+    fn test_from_gguf_loads_vocabulary() {
+        use std::io::Write;
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"GGUF");
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(&0u64.to_le_bytes());
+        buf.extend_from_slice(&3u64.to_le_bytes());
+
+        let write_string = |buf: &mut Vec<u8>, s: &str| {
+            buf.extend_from_slice(&(s.len() as u64).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+        };
+
+        write_string(&mut buf, "tokenizer.ggml.tokens");
+        buf.extend_from_slice(&9u32.to_le_bytes()); // ARRAY
+        buf.extend_from_slice(&8u32.to_le_bytes()); // STRING
+        buf.extend_from_slice(&4u64.to_le_bytes());
+        for t in ["a", "b", "ab", "<s>"] {
+            write_string(&mut buf, t);
+        }
+
+        write_string(&mut buf, "tokenizer.ggml.merges");
+        buf.extend_from_slice(&9u32.to_le_bytes());
+        buf.extend_from_slice(&8u32.to_le_bytes());
+        buf.extend_from_slice(&1u64.to_le_bytes());
+        write_string(&mut buf, "a b");
+
+        write_string(&mut buf, "tokenizer.ggml.bos_token_id");
+        buf.extend_from_slice(&4u32.to_le_bytes()); // UINT32
+        buf.extend_from_slice(&3u32.to_le_bytes());
+
+        let mut file = tempfile::NamedTempFile::with_suffix(".gguf").unwrap();
+        file.write_all(&buf).unwrap();
+
+        let tokenizer = TokenizerWrapper::from_gguf(file.path()).unwrap();
+        assert_eq!(tokenizer.count_tokens("ab"), 1);
+    }
 }
@@ -2,7 +2,11 @@
 //!
 //! Renders the main interface using Ratatui widgets.
 
+use crate::ansi;
 use crate::app::{App, ViewMode};
+use crate::jsontree::{TreeRow, TreeRowKind};
+use crate::markdown;
+use crate::theme::Theme;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -10,40 +14,11 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
-
-/// Theme colors for the UI
-pub struct Theme {
-    pub bg: Color,
-    pub fg: Color,
-    pub accent: Color,
-    pub error: Color,
-    pub warning: Color,
-    pub border: Color,
-    pub highlight: Color,
-    pub muted: Color,
-    pub duplicate: Color,
-}
-
-impl Default for Theme {
-    fn default() -> Self {
-        // Dracula-inspired dark theme
-        Self {
-            bg: Color::Rgb(40, 42, 54),
-            fg: Color::Rgb(248, 248, 242),
-            accent: Color::Rgb(139, 233, 253),
-            error: Color::Rgb(255, 85, 85),
-            warning: Color::Rgb(255, 184, 108),
-            border: Color::Rgb(98, 114, 164),
-            highlight: Color::Rgb(68, 71, 90),
-            muted: Color::Rgb(98, 114, 164),
-            duplicate: Color::Rgb(255, 170, 50), // Amber for duplicates
-        }
-    }
-}
+use unicode_width::UnicodeWidthStr;
 
 /// Render the entire UI
 pub fn render(frame: &mut Frame, app: &mut App) {
-    let theme = Theme::default();
+    let theme = app.theme.clone();
 
     // Update viewport height based on frame size
     app.set_viewport_height(frame.area().height as usize);
@@ -82,6 +57,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
 /// Render the main content area with dataset lines
 fn render_content(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    if app.view_mode == ViewMode::Tree {
+        render_tree_content(frame, app, area, theme);
+        return;
+    }
+
     let visible_lines = (area.height as usize).saturating_sub(2);
 
     let items: Vec<ListItem> = (0..visible_lines)
@@ -97,68 +77,100 @@ fn render_content(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 line_content.to_string()
             };
 
+            // Gutter status glyph: lint/dup take priority, otherwise a
+            // record-type hint (object/array/malformed JSON).
+            let (glyph, glyph_color) = gutter_glyph(app, line_idx, line_content, theme);
+
             // Create styled line based on view mode, lint status, and dedup status
-            let line: Line = if app.line_has_error(line_idx) {
-                // Error line - highlight in red (highest priority)
-                Line::from(vec![
+            let lines: Vec<Line> = if app.line_has_error(line_idx) {
+                // Error line - highlight in red (highest priority), with
+                // rustc-style caret annotations for the underlying lint
+                // errors on the rows beneath it.
+                let gutter = format!("{:>6} {} │ ", line_idx + 1, glyph);
+                let gutter_width = gutter.width();
+                let first_line = Line::from(vec![
+                    Span::styled(gutter, Style::default().fg(theme.error)),
                     Span::styled(
-                        format!("{:>6} │ ", line_idx + 1),
-                        Style::default().fg(theme.error),
-                    ),
-                    Span::styled(
-                        truncated,
+                        truncated.clone(),
                         Style::default()
                             .fg(theme.error)
                             .add_modifier(Modifier::BOLD),
                     ),
-                ])
+                ]);
+
+                // Annotation byte spans are computed against the full line;
+                // clamp them to the (possibly truncated) visible portion.
+                let visible_len = if truncated.ends_with("...") && truncated.len() < line_content.len() {
+                    display_width.saturating_sub(3)
+                } else {
+                    line_content.len()
+                }
+                .min(line_content.len());
+                let annotations: Vec<Annotation> = line_annotations(app, line_idx)
+                    .into_iter()
+                    .filter(|a| a.byte_start < visible_len)
+                    .map(|mut a| {
+                        a.byte_end = a.byte_end.min(visible_len);
+                        a
+                    })
+                    .collect();
+
+                let mut out = vec![first_line];
+                out.extend(render_annotation_lines(
+                    &line_content[..visible_len],
+                    gutter_width,
+                    &annotations,
+                    theme,
+                ));
+                out
             } else if app.line_is_duplicate(line_idx) {
-                // Duplicate line - highlight in amber
-                Line::from(vec![
-                    Span::styled(
-                        format!("{:>6} │ ", line_idx + 1),
-                        Style::default().fg(theme.duplicate),
-                    ),
-                    Span::styled(
-                        "DUP ",
-                        Style::default()
-                            .fg(Color::Rgb(40, 42, 54))
-                            .bg(theme.duplicate)
-                            .add_modifier(Modifier::BOLD),
-                    ),
+                // Duplicate line - highlight in amber; the gutter glyph
+                // carries the "DUP" hint instead of an inline text badge.
+                vec![Line::from(vec![
                     Span::styled(
-                        truncated,
+                        format!("{:>6} {} │ ", line_idx + 1, glyph),
                         Style::default().fg(theme.duplicate),
                     ),
-                ])
+                    Span::styled(truncated, Style::default().fg(theme.duplicate)),
+                ])]
             } else if app.view_mode == ViewMode::TokenXray {
                 // Token X-Ray mode
                 if let Some(ref tokenizer) = app.tokenizer {
                     let token_line = tokenizer.colorize_tokens(&truncated);
                     let mut spans = vec![Span::styled(
-                        format!("{:>6} │ ", line_idx + 1),
-                        Style::default().fg(theme.muted),
+                        format!("{:>6} {} │ ", line_idx + 1, glyph),
+                        Style::default().fg(glyph_color),
                     )];
                     spans.extend(token_line.spans);
-                    Line::from(spans)
+                    vec![Line::from(spans)]
                 } else {
-                    Line::from(vec![
+                    vec![Line::from(vec![
                         Span::styled(
-                            format!("{:>6} │ ", line_idx + 1),
-                            Style::default().fg(theme.muted),
+                            format!("{:>6} {} │ ", line_idx + 1, glyph),
+                            Style::default().fg(glyph_color),
                         ),
                         Span::styled(truncated, Style::default().fg(theme.fg)),
-                    ])
+                    ])]
                 }
+            } else if app.ansi_render {
+                // ANSI escape rendering: styled spans instead of raw escape
+                // bytes or JSON highlighting.
+                let rendered = ansi::render_ansi(&truncated);
+                let mut spans = vec![Span::styled(
+                    format!("{:>6} {} │ ", line_idx + 1, glyph),
+                    Style::default().fg(glyph_color),
+                )];
+                spans.extend(rendered.spans);
+                vec![Line::from(spans)]
             } else {
                 // Normal text mode with JSON syntax highlighting
                 let highlighted = highlight_json(&truncated, theme);
                 let mut spans = vec![Span::styled(
-                    format!("{:>6} │ ", line_idx + 1),
-                    Style::default().fg(theme.muted),
+                    format!("{:>6} {} │ ", line_idx + 1, glyph),
+                    Style::default().fg(glyph_color),
                 )];
                 spans.extend(highlighted.spans);
-                Line::from(spans)
+                vec![Line::from(spans)]
             };
 
             // Highlight selected line
@@ -168,7 +180,7 @@ fn render_content(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
                 Style::default()
             };
 
-            Some(ListItem::new(line).style(style))
+            Some(ListItem::new(lines).style(style))
         })
         .collect();
 
@@ -197,6 +209,119 @@ fn render_content(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     frame.render_widget(list, area);
 }
 
+/// Render `ViewMode::Tree`: the currently selected line's JSON flattened
+/// into one row per node, with expansion glyphs and key/value syntax
+/// highlighting — unlike the other view modes, this shows one record's
+/// structure rather than a page of dataset lines.
+fn render_tree_content(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let rows = app.tree_rows();
+
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "(not a JSON object/array — nothing to show in Tree view)",
+            Style::default().fg(theme.muted),
+        )))]
+    } else {
+        rows.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let style = if i == app.tree_cursor {
+                    Style::default().bg(theme.highlight)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(tree_row_line(row, theme)).style(style)
+            })
+            .collect()
+    };
+
+    let title = format!(
+        " Caret │ {} │ Record {} │ TREE  ",
+        app.dataset.path.split('/').next_back().unwrap_or("file"),
+        app.selected_line + 1,
+    );
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(Span::styled(title, Style::default().fg(theme.accent)))
+            .style(Style::default().bg(theme.bg)),
+    );
+
+    frame.render_widget(list, area);
+}
+
+/// Render one `TreeRow` as an indented, syntax-highlighted line: an
+/// expansion glyph for containers, the key (if any), and the value.
+fn tree_row_line(row: &TreeRow, theme: &Theme) -> Line<'static> {
+    let indent = "  ".repeat(row.depth);
+    let mut spans = vec![Span::raw(indent)];
+
+    let glyph = if row.expandable {
+        if row.expanded { "▾ " } else { "▸ " }
+    } else {
+        "  "
+    };
+    spans.push(Span::styled(glyph, Style::default().fg(theme.muted)));
+
+    if let Some(key) = &row.key {
+        spans.push(Span::styled(key.clone(), Style::default().fg(theme.accent)));
+        spans.push(Span::styled(": ", Style::default().fg(theme.fg)));
+    }
+
+    match &row.kind {
+        TreeRowKind::Object { len } => {
+            let text = if row.expanded {
+                "{".to_string()
+            } else {
+                format!("{{{} keys}}", len)
+            };
+            spans.push(Span::styled(text, Style::default().fg(theme.warning)));
+        }
+        TreeRowKind::Array { len } => {
+            let text = if row.expanded {
+                "[".to_string()
+            } else {
+                format!("[{} items]", len)
+            };
+            spans.push(Span::styled(text, Style::default().fg(theme.warning)));
+        }
+        TreeRowKind::String(s) => {
+            spans.push(Span::styled(
+                format!("\"{}\"", s),
+                Style::default().fg(Color::Rgb(241, 250, 140)), // Yellow for values
+            ));
+        }
+        TreeRowKind::Number(n) => {
+            spans.push(Span::styled(
+                n.clone(),
+                Style::default().fg(Color::Rgb(189, 147, 249)), // Purple for numbers
+            ));
+        }
+        TreeRowKind::Bool(b) => {
+            spans.push(Span::styled(
+                b.to_string(),
+                Style::default().fg(Color::Rgb(255, 121, 198)), // Pink for booleans
+            ));
+        }
+        TreeRowKind::Null => {
+            spans.push(Span::styled(
+                "null",
+                Style::default().fg(Color::Rgb(255, 121, 198)), // Pink, same family as bool
+            ));
+        }
+        TreeRowKind::Truncated { remaining } => {
+            spans.push(Span::styled(
+                format!("... {} more", remaining),
+                Style::default().fg(theme.muted),
+            ));
+        }
+    }
+
+    Line::from(spans)
+}
+
 /// Render the status bar
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
     let lint_count = app.lint_results.len();
@@ -218,6 +343,23 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         " No tokenizer ".to_string()
     };
 
+    // Rolling token-budget bar for the visible window (cheap to recompute
+    // every frame since `count_tokens_for_lines` reuses the offset cache),
+    // warning once the visible lines alone exceed the model's context
+    // window. Lines outside the viewport are not counted.
+    let budget_status = app.tokenizer.as_ref().map(|t| {
+        let lines: Vec<&str> = (0..app.viewport_height)
+            .filter_map(|i| app.dataset.get_line(app.scroll + i))
+            .collect();
+        let report = t.budget_report(lines);
+        (format!(" {} ", report.utilization_label()), report.exceeds_context())
+    });
+
+    let budget_style = match &budget_status {
+        Some((_, true)) => Style::default().fg(theme.error),
+        _ => Style::default().fg(theme.muted),
+    };
+
     let dedup_status = if let Some(ref result) = app.dedup_result {
         format!(
             " {} dups ({:.0}%) {:.0}ms ",
@@ -255,6 +397,11 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
         Span::styled(tokenizer_status, Style::default().fg(theme.muted)),
     ];
 
+    if let Some((label, _)) = &budget_status {
+        spans.push(Span::styled("|", Style::default().fg(theme.border)));
+        spans.push(Span::styled(label.clone(), budget_style));
+    }
+
     if !dedup_status.is_empty() {
         spans.push(Span::styled("|", Style::default().fg(theme.border)));
         spans.push(Span::styled(dedup_status, dedup_style));
@@ -335,7 +482,11 @@ fn render_help_popup(frame: &mut Frame, theme: &Theme) {
         ]),
         Line::from(vec![
             Span::styled("  Enter    ", Style::default().fg(theme.accent)),
-            Span::raw("Toggle detail panel (pretty JSON)"),
+            Span::raw("Toggle detail panel (pretty JSON); in TREE mode, toggles the node under the cursor"),
+        ]),
+        Line::from(vec![
+            Span::styled("  j/k/Space", Style::default().fg(theme.accent)),
+            Span::raw("In TREE mode: move the tree cursor / toggle the node under it"),
         ]),
         Line::from(""),
         Line::from(Span::styled(
@@ -348,6 +499,18 @@ fn render_help_popup(frame: &mut Frame, theme: &Theme) {
             Span::styled("  D        ", Style::default().fg(theme.duplicate)),
             Span::raw("Toggle dedup scan (SimHash)"),
         ]),
+        Line::from(vec![
+            Span::styled("  T        ", Style::default().fg(theme.accent)),
+            Span::raw("Cycle theme (dracula/solarized/gruvbox)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  M        ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle Markdown rendering in detail panel"),
+        ]),
+        Line::from(vec![
+            Span::styled("  A        ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle ANSI escape rendering in Text view and detail panel"),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("  ?        ", Style::default().fg(theme.muted)),
@@ -380,21 +543,34 @@ fn render_detail_panel(frame: &mut Frame, app: &mut App, area: Rect, theme: &The
         return;
     }
 
-    let pretty_json = app.current_line_pretty();
-
-    // Default: show pretty JSON with syntax highlighting
-    let lines: Vec<Line> = pretty_json
-        .lines()
-        .map(|line| highlight_json(line, theme))
-        .collect();
+    let lines: Vec<Line> = if app.show_markdown {
+        render_record_markdown(app, theme)
+    } else if app.ansi_render {
+        let pretty_json = app.current_line_pretty();
+        pretty_json.lines().map(ansi::render_ansi).collect()
+    } else {
+        let pretty_json = app.current_line_pretty();
+        pretty_json
+            .lines()
+            .map(|line| highlight_json(line, theme))
+            .collect()
+    };
 
     let dup_label = if app.line_is_duplicate(app.selected_line) {
         " [DUPLICATE]"
     } else {
         ""
     };
+    let md_label = if app.show_markdown { " [MD]" } else { "" };
+    let ansi_label = if app.ansi_render { " [ANSI]" } else { "" };
 
-    let title = format!(" Record {}{} ", app.selected_line + 1, dup_label);
+    let title = format!(
+        " Record {}{}{}{} ",
+        app.selected_line + 1,
+        dup_label,
+        md_label,
+        ansi_label
+    );
 
     let paragraph = Paragraph::new(lines)
         .block(
@@ -409,6 +585,40 @@ fn render_detail_panel(frame: &mut Frame, app: &mut App, area: Rect, theme: &The
     frame.render_widget(paragraph, area);
 }
 
+/// Render the selected record's fields for the Markdown detail view: string
+/// fields (`content`, `instruction`, `response`, ...) are rendered through
+/// [`markdown::render_markdown`], everything else falls back to the usual
+/// JSON syntax highlighting.
+fn render_record_markdown(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    let Some(raw) = app.current_line_content() else {
+        return Vec::new();
+    };
+
+    let Ok(serde_json::Value::Object(obj)) = serde_json::from_str::<serde_json::Value>(raw) else {
+        // Not a JSON object (or invalid JSON) - fall back to plain text.
+        return raw.lines().map(|l| Line::from(l.to_string())).collect();
+    };
+
+    let mut lines = Vec::new();
+    for (key, field_value) in &obj {
+        lines.push(Line::from(Span::styled(
+            format!("{}:", key),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        match field_value {
+            serde_json::Value::String(s) => lines.extend(markdown::render_markdown(s, theme)),
+            other => {
+                let pretty = serde_json::to_string_pretty(other).unwrap_or_default();
+                lines.extend(pretty.lines().map(|l| highlight_json(l, theme)));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
 /// Render token X-Ray with hover-style details (selected token info at bottom)
 fn render_token_xray_hover(
     frame: &mut Frame,
@@ -418,14 +628,10 @@ fn render_token_xray_hover(
 ) {
     use crate::tokenizer::TokenInfo;
 
-    // Color palette for tokens
-    const TOKEN_COLORS: [Color; 4] = [
-        Color::Rgb(70, 130, 180),  // Steel Blue
-        Color::Rgb(60, 60, 60),    // Dark Gray
-        Color::Rgb(100, 149, 237), // Cornflower Blue
-        Color::Rgb(80, 80, 80),    // Medium Gray
-    ];
-    const HIGHLIGHT_COLOR: Color = Color::Rgb(255, 200, 50); // Gold for selected
+    // Color palette for tokens, and the selected-token highlight, both
+    // theme-configurable (see `theme::Theme::token_colors`).
+    let token_colors = &app.theme.token_colors;
+    let highlight_color: Color = app.theme.token_highlight.into();
 
     // Collect all data we need from app first (before mutation)
     let (all_tokens, pretty_json, line_tokenizations): (Vec<TokenInfo>, String, Vec<Vec<TokenInfo>>) = {
@@ -487,10 +693,10 @@ fn render_token_xray_hover(
                 token.byte_start == sel.byte_start && token.byte_end == sel.byte_end
             });
 
-            let bg_color = if is_selected {
-                HIGHLIGHT_COLOR
+            let bg_color: Color = if is_selected {
+                highlight_color
             } else {
-                TOKEN_COLORS[i % TOKEN_COLORS.len()]
+                token_colors[i % token_colors.len()].into()
             };
 
             let fg_color = if is_selected {
@@ -534,7 +740,7 @@ fn render_token_xray_hover(
             Span::styled(" Token: ", Style::default().fg(theme.muted)),
             Span::styled(
                 format!("\"{}\"", tok.text.replace('\n', "\\n").replace('\t', "\\t")),
-                Style::default().fg(HIGHLIGHT_COLOR).add_modifier(Modifier::BOLD),
+                Style::default().fg(highlight_color).add_modifier(Modifier::BOLD),
             ),
             Span::styled(" │ ID: ", Style::default().fg(theme.muted)),
             Span::styled(
@@ -569,6 +775,152 @@ fn render_token_xray_hover(
     frame.render_widget(status_bar, chunks[1]);
 }
 
+/// Pick the gutter status glyph and its color for `line_idx`, following
+/// Helix's icon approach: lint errors and duplicates take priority (they
+/// already get a dedicated highlight color), otherwise the glyph hints at
+/// the record's JSON shape. The glyph set itself - Nerd Font icons or the
+/// ASCII fallback - comes from `theme.glyphs` (see `Theme::use_nerd_font`).
+fn gutter_glyph(app: &App, line_idx: usize, content: &str, theme: &Theme) -> (String, Color) {
+    if app.line_has_error(line_idx) {
+        return (theme.glyphs.error.clone(), theme.error);
+    }
+    if app.line_is_duplicate(line_idx) {
+        return (theme.glyphs.duplicate.clone(), theme.duplicate);
+    }
+    match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(serde_json::Value::Object(_)) => (theme.glyphs.object.clone(), theme.muted),
+        Ok(serde_json::Value::Array(_)) => (theme.glyphs.array.clone(), theme.muted),
+        Ok(_) => (theme.glyphs.valid.clone(), Color::Rgb(80, 250, 123)),
+        Err(_) => (theme.glyphs.malformed.clone(), theme.error),
+    }
+}
+
+/// A single lint annotation to render as a rustc-style caret underline
+/// beneath a source line (see [`render_annotation_lines`]).
+struct Annotation {
+    byte_start: usize,
+    byte_end: usize,
+    severity: &'static str,
+    message: String,
+}
+
+/// Collect the annotations for `line_idx`, one per lint error found on
+/// that line.
+fn line_annotations(app: &App, line_idx: usize) -> Vec<Annotation> {
+    let Some(content) = app.dataset.get_line(line_idx) else {
+        return Vec::new();
+    };
+
+    app.lint_results
+        .iter()
+        .filter(|r| r.line == line_idx)
+        .map(|r| {
+            let (byte_start, byte_end) = r.error.byte_span(content);
+            Annotation {
+                byte_start,
+                byte_end,
+                severity: r.error.severity(),
+                message: r.error.message(),
+            }
+        })
+        .collect()
+}
+
+/// Render rustc-style caret (`^^^^`) underlines beneath `source`: one row of
+/// carets, followed by one `└─ message` row per annotation. Messages are
+/// drawn closest annotation first; annotations further left that haven't
+/// had their row yet get a `│` connector carried down through the rows
+/// above them, mirroring how rustc lays out multi-span diagnostics.
+///
+/// `gutter_width` is the display width of the `"{:>6} {glyph} │ "` gutter prefix so
+/// that caret columns line up under the source text rather than under the
+/// line number.
+fn render_annotation_lines(
+    source: &str,
+    gutter_width: usize,
+    annotations: &[Annotation],
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if annotations.is_empty() {
+        return Vec::new();
+    }
+
+    // Map each annotation's byte span to a (start_col, end_col) display
+    // column span, measured from the start of `source`.
+    let mut placed: Vec<(usize, usize, &Annotation)> = annotations
+        .iter()
+        .map(|a| {
+            let start = source[..a.byte_start.min(source.len())].width();
+            let end = if a.byte_end > a.byte_start {
+                source[..a.byte_end.min(source.len())]
+                    .width()
+                    .max(start + 1)
+            } else {
+                start + 1
+            };
+            (start, end, a)
+        })
+        .collect();
+    placed.sort_by_key(|(start, _, _)| *start);
+
+    let gutter_pad = " ".repeat(gutter_width);
+    let color_for = |severity: &str| -> Color {
+        if severity == "ERROR" {
+            theme.error
+        } else {
+            theme.warning
+        }
+    };
+
+    // Caret row: a run of `^` per non-overlapping annotation.
+    let mut caret_spans = vec![Span::raw(gutter_pad.clone())];
+    let mut col = 0usize;
+    for (start, end, ann) in &placed {
+        if *start < col {
+            continue; // overlaps a previous span; don't double-draw
+        }
+        if *start > col {
+            caret_spans.push(Span::raw(" ".repeat(start - col)));
+        }
+        caret_spans.push(Span::styled(
+            "^".repeat(end - start),
+            Style::default()
+                .fg(color_for(ann.severity))
+                .add_modifier(Modifier::BOLD),
+        ));
+        col = *end;
+    }
+    let mut lines = vec![Line::from(caret_spans)];
+
+    // Label rows, nearest annotation first (rendered last / closest to the
+    // carets), with `│` connectors for annotations still awaiting their row.
+    for i in (0..placed.len()).rev() {
+        let (start, _end, ann) = placed[i];
+        let mut spans = vec![Span::raw(gutter_pad.clone())];
+        let mut col = 0usize;
+        for (left_start, _, left_ann) in &placed[..i] {
+            if *left_start >= col {
+                spans.push(Span::raw(" ".repeat(left_start - col)));
+                spans.push(Span::styled(
+                    "│",
+                    Style::default().fg(color_for(left_ann.severity)),
+                ));
+                col = left_start + 1;
+            }
+        }
+        if start >= col {
+            spans.push(Span::raw(" ".repeat(start - col)));
+        }
+        spans.push(Span::styled(
+            format!("└─ {}", ann.message),
+            Style::default().fg(color_for(ann.severity)),
+        ));
+        lines.push(Line::from(spans));
+    }
+
+    lines
+}
+
 /// Basic JSON syntax highlighting
 fn highlight_json(text: &str, theme: &Theme) -> Line<'static> {
     let mut spans = Vec::new();
@@ -0,0 +1,102 @@
+//! Golden-file regression harness for `Fixer`, mirroring rustfix's
+//! `parse_and_replace` fixture tests: every `tests/fixtures/*.jsonl` file is
+//! run through `Fixer::fix_line` and compared against a committed
+//! `.fixed.jsonl` (the output lines) and `.fixes.json` (the ordered
+//! `FixType`s applied to each line) snapshot.
+//!
+//! Set `CARET_RECORD=1` to (re)write the snapshots instead of checking them.
+//! Do this once when adding a new fixture, then commit the snapshot
+//! alongside it.
+
+use caret::fixer::{Applicability, FixResult, FixType, Fixer};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[test]
+fn fixer_snapshots_match() {
+    let dir = fixture_dir();
+    let record = std::env::var_os("CARET_RECORD").is_some();
+    // Unsafe fixes included, since the snapshot exists precisely to catch
+    // drift in the fragile think-tag heuristics.
+    let fixer = Fixer::new().with_min_applicability(Applicability::Unsafe);
+
+    let mut fixture_count = 0;
+    for entry in fs::read_dir(&dir).expect("tests/fixtures should exist") {
+        let path = entry.expect("readable dir entry").path();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        if !name.ends_with(".jsonl") || name.ends_with(".fixed.jsonl") {
+            continue;
+        }
+        fixture_count += 1;
+
+        let input = fs::read_to_string(&path).expect("fixture should be readable");
+        let mut fixed_lines = Vec::new();
+        let mut fixes_per_line: Vec<Vec<FixType>> = Vec::new();
+
+        for line in input.lines() {
+            match fixer.fix_line(line) {
+                FixResult::Fixed { line: fixed, applied, .. } => {
+                    fixed_lines.push(fixed);
+                    fixes_per_line.push(applied);
+                }
+                FixResult::Unchanged(unchanged) => {
+                    fixed_lines.push(unchanged);
+                    fixes_per_line.push(Vec::new());
+                }
+                FixResult::Skipped(_) => {
+                    fixed_lines.push(line.to_string());
+                    fixes_per_line.push(Vec::new());
+                }
+            }
+        }
+
+        let stem = name.strip_suffix(".jsonl").unwrap();
+        let fixed_path = dir.join(format!("{stem}.fixed.jsonl"));
+        let fixes_path = dir.join(format!("{stem}.fixes.json"));
+
+        let fixed_snapshot = format!("{}\n", fixed_lines.join("\n"));
+        let fixes_snapshot = serde_json::to_string_pretty(&fixes_per_line).expect("fix types should serialize") + "\n";
+
+        if record {
+            fs::write(&fixed_path, &fixed_snapshot).expect("should write .fixed.jsonl snapshot");
+            fs::write(&fixes_path, &fixes_snapshot).expect("should write .fixes.json snapshot");
+            continue;
+        }
+
+        let expected_fixed = fs::read_to_string(&fixed_path)
+            .unwrap_or_else(|_| panic!("missing snapshot {fixed_path:?} - run with CARET_RECORD=1 to create it"));
+        let expected_fixes = fs::read_to_string(&fixes_path)
+            .unwrap_or_else(|_| panic!("missing snapshot {fixes_path:?} - run with CARET_RECORD=1 to create it"));
+
+        assert_text_eq(&name, "fixed output", &expected_fixed, &fixed_snapshot);
+        assert_text_eq(&name, "fix types", &expected_fixes, &fixes_snapshot);
+    }
+
+    assert!(fixture_count > 0, "expected at least one tests/fixtures/*.jsonl fixture");
+}
+
+/// Assert two snapshot bodies are equal, printing a line-oriented diff on
+/// mismatch rather than relying on `assert_eq!`'s unreadable output for
+/// multi-line strings.
+fn assert_text_eq(fixture: &str, kind: &str, expected: &str, actual: &str) {
+    if expected == actual {
+        return;
+    }
+
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let e = expected_lines.get(i).copied().unwrap_or("<missing>");
+        let a = actual_lines.get(i).copied().unwrap_or("<missing>");
+        if e != a {
+            diff.push_str(&format!("  line {}:\n  - {}\n  + {}\n", i + 1, e, a));
+        }
+    }
+
+    panic!("{fixture}: {kind} snapshot mismatch (run with CARET_RECORD=1 to update)\n{diff}");
+}